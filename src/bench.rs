@@ -0,0 +1,293 @@
+//! Measure how well a petitioner plays, over many games.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{oracle::memory_oracle::MemoryOracle, Outcome};
+
+/// Choose `count` distinct targets at random from `words`, reproducibly for a given seed.
+pub fn sample(words: &[String], count: usize, seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    words.choose_multiple(&mut rng, count).cloned().collect()
+}
+
+/// Derive the seed for a single game from the seed of a whole run.
+///
+/// This depends only on the game's position in the list of targets, so a game plays out the same
+/// way however the games are scheduled.
+fn game_seed(seed: u64, idx: usize) -> u64 {
+    seed ^ (idx as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+}
+
+/// A game which the petitioner did not win.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    pub target: String,
+    /// why the game was lost
+    pub reason: String,
+}
+
+/// Aggregate results of many games.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// how many games were won in each number of guesses
+    pub histogram: BTreeMap<usize, usize>,
+    /// every game which was not won
+    pub failures: Vec<Failure>,
+    /// the total time spent playing
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// The number of games played.
+    pub fn games(&self) -> usize {
+        self.wins() + self.failures.len()
+    }
+
+    /// The number of games won.
+    pub fn wins(&self) -> usize {
+        self.histogram.values().sum()
+    }
+
+    /// The fraction of games won, from 0 to 1, if any games were played.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.games() > 0).then(|| self.wins() as f64 / self.games() as f64)
+    }
+
+    /// The mean number of guesses in games which were won.
+    pub fn mean_guesses(&self) -> Option<f64> {
+        let total: usize = self
+            .histogram
+            .iter()
+            .map(|(guesses, games)| guesses * games)
+            .sum();
+        (self.wins() > 0).then(|| total as f64 / self.wins() as f64)
+    }
+
+    /// The median number of guesses in games which were won.
+    pub fn median_guesses(&self) -> Option<usize> {
+        let middle = self.wins().checked_sub(1)? / 2;
+        let mut seen = 0;
+        self.histogram.iter().find_map(|(&guesses, &games)| {
+            seen += games;
+            (seen > middle).then_some(guesses)
+        })
+    }
+
+    /// The mean time spent per game, if any games were played.
+    pub fn time_per_game(&self) -> Option<Duration> {
+        (self.games() > 0).then(|| self.elapsed / self.games() as u32)
+    }
+
+    /// Add the outcome of a single game.
+    fn record(&mut self, target: String, outcome: Result<usize, String>) {
+        match outcome {
+            Ok(guesses) => *self.histogram.entry(guesses).or_default() += 1,
+            Err(reason) => self.failures.push(Failure { target, reason }),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BAR_WIDTH: usize = 40;
+
+        writeln!(f, "games:          {}", self.games())?;
+        match self.win_rate() {
+            Some(win_rate) => writeln!(f, "win rate:       {:.2}%", 100.0 * win_rate)?,
+            None => writeln!(f, "win rate:       -")?,
+        }
+        match (self.mean_guesses(), self.median_guesses()) {
+            (Some(mean), Some(median)) => {
+                writeln!(f, "mean guesses:   {:.3}", mean)?;
+                writeln!(f, "median guesses: {}", median)?;
+            }
+            _ => {
+                writeln!(f, "mean guesses:   -")?;
+                writeln!(f, "median guesses: -")?;
+            }
+        }
+        match self.time_per_game() {
+            Some(time) => writeln!(f, "time per game:  {:?}", time)?,
+            None => writeln!(f, "time per game:  -")?,
+        }
+
+        let most = self.histogram.values().copied().max().unwrap_or_default();
+        for (guesses, &games) in self.histogram.iter() {
+            let width = (games * BAR_WIDTH).div_ceil(most.max(1));
+            writeln!(f, "{:>3} | {} {}", guesses, "#".repeat(width), games)?;
+        }
+
+        if !self.failures.is_empty() {
+            writeln!(f, "failures:")?;
+            for failure in self.failures.iter() {
+                writeln!(f, "  {}: {}", failure.target, failure.reason)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Play a single game against a `MemoryOracle`, returning the number of guesses if it was won.
+fn play_one<Petitioner, ConfigureOracle, MakePetitioner>(
+    target: &str,
+    seed: u64,
+    configure_oracle: &mut ConfigureOracle,
+    make_petitioner: &mut MakePetitioner,
+) -> Result<usize, String>
+where
+    Petitioner: crate::Petitioner + ?Sized,
+    ConfigureOracle: FnMut(&mut MemoryOracle),
+    MakePetitioner: FnMut(usize, u64) -> Result<Box<Petitioner>, crate::petitioner::Error>,
+{
+    let mut oracle = MemoryOracle::with_target(target.to_owned());
+    configure_oracle(&mut oracle);
+    let mut petitioner =
+        make_petitioner(target.chars().count(), seed).map_err(|err| err.to_string())?;
+    let record = crate::play(&*oracle, &mut *petitioner, &mut []);
+    match record.outcome {
+        Outcome::Won => Ok(record.guesses()),
+        Outcome::Lost { .. } => Err(String::from("ran out of guesses")),
+        Outcome::Error(err) => Err(crate::record::describe(&err)),
+    }
+}
+
+/// Play one game against a `MemoryOracle` for each target, and report the results.
+///
+/// `configure_oracle` may adjust each oracle before the game begins, for example to set
+/// `rules.max_guesses`. `make_petitioner` creates a fresh petitioner for each game, given the word
+/// length and a seed for any randomness it needs. Each game's seed is derived from `seed`, so a
+/// petitioner which respects it plays identically on every run.
+pub fn run<Petitioner, ConfigureOracle, MakePetitioner>(
+    targets: &[String],
+    seed: u64,
+    mut configure_oracle: ConfigureOracle,
+    mut make_petitioner: MakePetitioner,
+) -> Report
+where
+    Petitioner: crate::Petitioner + ?Sized,
+    ConfigureOracle: FnMut(&mut MemoryOracle),
+    MakePetitioner: FnMut(usize, u64) -> Result<Box<Petitioner>, crate::petitioner::Error>,
+{
+    let mut report = Report::default();
+    let start = Instant::now();
+    for (idx, target) in targets.iter().enumerate() {
+        let outcome = play_one(
+            target,
+            game_seed(seed, idx),
+            &mut configure_oracle,
+            &mut make_petitioner,
+        );
+        report.record(target.clone(), outcome);
+    }
+    report.elapsed = start.elapsed();
+    report
+}
+
+/// As [`run`], but spread the games across `threads` worker threads.
+///
+/// Each game is independent, so the report is the same as that of a sequential run with the
+/// same seed, apart from timing: failures are listed in the order of `targets` either way.
+/// `elapsed` is wall-clock time, so `time_per_game` measures throughput.
+pub fn run_parallel<Petitioner, ConfigureOracle, MakePetitioner>(
+    targets: &[String],
+    seed: u64,
+    threads: usize,
+    configure_oracle: ConfigureOracle,
+    make_petitioner: MakePetitioner,
+) -> Report
+where
+    Petitioner: crate::Petitioner + ?Sized,
+    ConfigureOracle: Fn(&mut MemoryOracle) + Sync,
+    MakePetitioner: Fn(usize, u64) -> Result<Box<Petitioner>, crate::petitioner::Error> + Sync,
+{
+    // workers claim the next unplayed target until there are none left
+    let next = AtomicUsize::new(0);
+    let start = Instant::now();
+    let mut outcomes: Vec<(usize, Result<usize, String>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        let target = match targets.get(idx) {
+                            Some(target) => target,
+                            None => return outcomes,
+                        };
+                        let outcome = play_one(
+                            target,
+                            game_seed(seed, idx),
+                            &mut &configure_oracle,
+                            &mut &make_petitioner,
+                        );
+                        outcomes.push((idx, outcome));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("benchmark worker panicked"))
+            .collect()
+    });
+    outcomes.sort_by_key(|(idx, _)| *idx);
+
+    let mut report = Report {
+        elapsed: start.elapsed(),
+        ..Report::default()
+    };
+    for (idx, outcome) in outcomes {
+        report.record(targets[idx].clone(), outcome);
+    }
+    report
+}
+
+#[cfg(all(test, feature = "dict_solver"))]
+mod tests {
+    use super::*;
+    use crate::petitioner::{dict_solver::DictSolver, strategy};
+    use rand::Rng;
+    use std::sync::Arc;
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // a small alphabet makes for many similar words
+        let alphabet = b"abc";
+        let mut words: Vec<String> = (0..200)
+            .map(|_| {
+                (0..5)
+                    .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+                    .collect()
+            })
+            .collect();
+        words.sort();
+        words.dedup();
+        let targets = sample(&words, 40, 1);
+        let dictionary: Arc<[String]> = words.into();
+
+        for name in ["random", "frequency", "minimax"] {
+            // with so few guesses, both wins and failures are compared
+            let configure_oracle = |oracle: &mut MemoryOracle| oracle.rules.max_guesses = Some(2);
+            let make_petitioner = |word_length, seed| {
+                let mut solver = DictSolver::with_words(word_length, Arc::clone(&dictionary));
+                solver.seed(seed);
+                solver.strategy = strategy::by_name(name).expect("strategy exists");
+                Ok(solver)
+            };
+            let sequential = run(&targets, 7, configure_oracle, make_petitioner);
+            let parallel = run_parallel(&targets, 7, 4, configure_oracle, make_petitioner);
+
+            assert_eq!(sequential.games(), targets.len());
+            assert_eq!(sequential.histogram, parallel.histogram, "{name}");
+            assert_eq!(sequential.failures, parallel.failures, "{name}");
+        }
+    }
+}
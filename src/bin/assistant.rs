@@ -0,0 +1,99 @@
+use std::io::{self, BufRead, Write};
+
+use wordler::{
+    oracle::{Disposition, Feedback},
+    petitioner::{dict_solver::DictSolver, strategy},
+    Config, Petitioner,
+};
+
+/// Print `question` and read a trimmed line in reply, or `None` at the end of input.
+fn prompt(question: &str) -> Result<Option<String>, io::Error> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", question)?;
+    stdout.flush()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_owned()))
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut strategy = strategy::by_name("entropy").expect("entropy strategy exists");
+    let mut config = Config::default();
+    let mut list_up_to = 20;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--strategy" => {
+                strategy = strategy::parse(&args.next().ok_or("--strategy requires a name")?)?
+            }
+            "--list" => list_up_to = args.next().ok_or("--list requires a number")?.parse()?,
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+    let word_length = config.word_length;
+
+    let mut solver = DictSolver::new(&config)?;
+    solver.strategy = strategy;
+
+    println!("Enter each guess you make, then its colors: g or 🟩 for green, y or 🟨 for yellow,");
+    println!("and b, . or ⬛ for grey. Enter nothing to quit.");
+    loop {
+        let candidates = solver.candidates();
+        match candidates.len() {
+            0 => {
+                println!("no words fit; check the colors you entered");
+                return Ok(());
+            }
+            1 => println!("the word must be {}", candidates[0]),
+            n if n <= list_up_to => println!("{} words fit: {}", n, candidates.join(", ")),
+            n => println!("{} words fit", n),
+        }
+        if candidates.len() > 1 {
+            if let Some(suggestion) = solver.suggest() {
+                println!("try: {}", suggestion);
+            }
+        }
+
+        let guess = match prompt("guess:  ")? {
+            Some(guess) if !guess.is_empty() => guess.to_lowercase(),
+            _ => return Ok(()),
+        };
+        if guess.chars().count() != word_length {
+            println!("guesses must have {} letters", word_length);
+            continue;
+        }
+        let feedback = loop {
+            let colors = match prompt("colors: ")? {
+                Some(colors) => colors,
+                None => return Ok(()),
+            };
+            match colors.parse::<Feedback>() {
+                Ok(feedback)
+                    if feedback
+                        .iter()
+                        .any(|disp| matches!(disp, Disposition::Missing | Disposition::Extra)) =>
+                {
+                    println!("colors must be green, yellow or grey")
+                }
+                Ok(feedback) if feedback.len() == word_length => break feedback,
+                Ok(feedback) => println!(
+                    "expected colors for {} letters but found {}",
+                    word_length,
+                    feedback.len()
+                ),
+                Err(err) => println!("{}", err),
+            }
+        };
+        if feedback.is_correct() {
+            println!("solved!");
+            return Ok(());
+        }
+        solver.observe(&guess, feedback)?;
+    }
+}
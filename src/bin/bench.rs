@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use wordler::{
+    bench,
+    petitioner::{dict_solver::DictSolver, strategy},
+    wordlist, Config,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut strategy_name = String::from("random");
+    let mut config = Config {
+        max_guesses: Some(6),
+        ..Config::default()
+    };
+    let mut sample: Option<usize> = None;
+    let mut threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        let mut value = |name: &str| args.next().ok_or(format!("{} requires a value", name));
+        match arg.as_str() {
+            "--strategy" => {
+                strategy_name = value("--strategy")?;
+                strategy::parse(&strategy_name)?;
+            }
+            "--sample" => sample = Some(value("--sample")?.parse()?),
+            "--threads" => threads = value("--threads")?.parse()?,
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+    let seed = config.seed.unwrap_or_default();
+    let word_length = config.word_length;
+    let hard_mode = config.hard_mode;
+
+    let mut targets = wordlist::answers_with(
+        &config.answers,
+        &config.words,
+        word_length,
+        &config.normalizer,
+    )?;
+    if let Some(count) = sample {
+        targets = bench::sample(&targets, count, seed);
+    }
+    // shared by every game's solver
+    let dictionary: Arc<[String]> = config
+        .words
+        .load_length_with(word_length, &config.normalizer)?
+        .into();
+
+    let report = bench::run_parallel(
+        &targets,
+        seed,
+        threads,
+        |oracle| {
+            oracle.rules.max_guesses = config.max_guesses;
+            oracle.rules.hard_mode = hard_mode;
+        },
+        |word_length, seed| {
+            let mut solver = DictSolver::with_words(word_length, Arc::clone(&dictionary));
+            solver.seed(seed);
+            solver.hard_mode = hard_mode;
+            solver.strategy = strategy::by_name(&strategy_name).expect("strategy name was checked");
+            Ok(solver)
+        },
+    );
+    print!("{}", report);
+    Ok(())
+}
@@ -1,21 +1,114 @@
 use wordler::{
-    oracle::{memory_oracle::MemoryOracle, Disposition},
-    petitioner::dict_solver::DictSolver,
-    print_feedback, wordle_config,
+    observer::PrintFeedback,
+    oracle::memory_oracle::MemoryOracle,
+    petitioner::{dict_solver::DictSolver, strategy},
+    play, record,
+    share::{self, ShareOptions},
+    Config, Oracle, Outcome, Petitioner,
 };
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let answer = wordle_config::<MemoryOracle, DictSolver, _, _>(
-        true,
-        |oracle| oracle.max_guesses = Some(6),
-        |_| {},
-    )?;
-    #[cfg(not(feature = "pretty_feedback"))]
-    println!("bot solver wins! ({})", answer);
-    #[cfg(feature = "pretty_feedback")]
-    {
-        let all_correct = vec![Disposition::Correct; answer.chars().count()];
-        print_feedback(&answer, &all_correct);
+    let mut strategy = strategy::by_name("random").expect("random strategy exists");
+    let mut config = Config {
+        max_guesses: Some(6),
+        ..Config::default()
+    };
+    let mut target: Option<String> = None;
+    let mut length_given = false;
+    let mut share: Option<ShareOptions> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        length_given |= arg == "--length";
+        if config.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--strategy" => {
+                strategy = strategy::parse(&args.next().ok_or("--strategy requires a name")?)?
+            }
+            "--target" => target = Some(args.next().ok_or("--target requires a word")?),
+            "--share" => {
+                share.get_or_insert_with(ShareOptions::default);
+            }
+            "--high-contrast" => {
+                share
+                    .get_or_insert_with(ShareOptions::default)
+                    .high_contrast = true
+            }
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+
+    let seed = *config.seed.get_or_insert_with(rand::random);
+    println!("seed: {}", seed);
+
+    let target = match target {
+        Some(target) => {
+            let target = config
+                .normalizer
+                .normalize(&target)
+                .ok_or_else(|| format!("target {:?} is not a valid word", target))?;
+            let length = target.chars().count();
+            if length_given && length != config.word_length {
+                return Err(format!(
+                    "target {:?} has {} letters, but --length is {}",
+                    target, length, config.word_length
+                )
+                .into());
+            }
+            Some(target)
+        }
+        None => None,
+    };
+
+    let oracle = match &target {
+        Some(target) => {
+            let mut oracle = MemoryOracle::with_target(target.clone());
+            oracle.rules.max_guesses = config.max_guesses;
+            oracle.rules.hard_mode = config.hard_mode;
+            oracle
+        }
+        None => MemoryOracle::new(&config)?,
+    };
+    config.word_length = oracle.word_length()?;
+    let mut solver = DictSolver::new(&config)?;
+    solver.strategy = strategy;
+    if let Some(target) = &target {
+        // the solver could never guess it
+        if !solver.candidates().contains(target) {
+            return Err(format!("target {:?} is not in the word list", target).into());
+        }
+    }
+
+    let record = play(&*oracle, &mut *solver, &mut [&mut PrintFeedback]);
+    match &record.outcome {
+        Outcome::Won => {
+            let answer = record.target().unwrap_or_default();
+            #[cfg(not(feature = "pretty_feedback"))]
+            println!("bot solver wins! ({})", answer);
+            #[cfg(feature = "pretty_feedback")]
+            {
+                wordler::print_feedback(
+                    answer,
+                    &wordler::oracle::Feedback::correct(answer.chars().count()),
+                );
+            }
+        }
+        Outcome::Lost { target } => {
+            println!(
+                "bot solver loses! (the word was {})",
+                target.as_deref().unwrap_or("a secret")
+            );
+        }
+        Outcome::Error(err) => return Err(record::describe(err).into()),
+    }
+    if let Some(options) = share {
+        let options = ShareOptions {
+            max_guesses: config.max_guesses,
+            hard_mode: config.hard_mode,
+            ..options
+        };
+        println!("\n{}", share::grid(&record, &options));
     }
     Ok(())
 }
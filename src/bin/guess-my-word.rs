@@ -0,0 +1,45 @@
+use wordler::{
+    oracle::human_oracle::HumanOracle,
+    petitioner::{dict_solver::DictSolver, strategy},
+    play, record, Config, Oracle, Outcome, Petitioner,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut strategy = strategy::by_name("entropy").expect("entropy strategy exists");
+    let mut config = Config {
+        max_guesses: Some(6),
+        ..Config::default()
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--strategy" => {
+                strategy = strategy::parse(&args.next().ok_or("--strategy requires a name")?)?
+            }
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+
+    let oracle = HumanOracle::new(&config)?;
+    config.word_length = oracle.word_length()?;
+    let mut solver = DictSolver::new(&config)?;
+    solver.strategy = strategy;
+
+    let record = play(&*oracle, &mut *solver, &mut []);
+    match &record.outcome {
+        Outcome::Won => println!(
+            "got it! ({}, in {} guesses)",
+            record.target().unwrap_or_default(),
+            record.guesses()
+        ),
+        Outcome::Lost {
+            target: Some(target),
+        } => println!("you win! (your word was {})", target),
+        Outcome::Lost { target: None } => println!("you win!"),
+        Outcome::Error(err) => return Err(record::describe(err).into()),
+    }
+    Ok(())
+}
@@ -1,7 +1,41 @@
-use wordler::wordlist::load;
+use std::path::PathBuf;
+
+use wordler::wordlist::{download, import, index, load, List, Normalizer};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let words = load()?;
-    println!("cached {} words", words.count());
+    let mut answers: Option<PathBuf> = None;
+    let mut words: Option<PathBuf> = None;
+    let mut normalizer = Normalizer::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if normalizer.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--answers" => answers = Some(args.next().ok_or("--answers requires a path")?.into()),
+            "--words" => words = Some(args.next().ok_or("--words requires a path")?.into()),
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+
+    for (list, path) in [(List::Answers, answers), (List::Allowed, words)] {
+        match path {
+            Some(path) => {
+                let report = import(list, path, &normalizer)?;
+                println!("cached {} list: {}", list.name(), report);
+            }
+            None if normalizer != Normalizer::default() => {
+                let report = download(list, &normalizer)?;
+                println!("cached {} list: {}", list.name(), report);
+            }
+            None => println!("cached {} {} words", load(list)?.count(), list.name()),
+        }
+        let buckets = index::build(list)?;
+        println!(
+            "indexed {} list by length into {} buckets",
+            list.name(),
+            buckets
+        );
+    }
     Ok(())
 }
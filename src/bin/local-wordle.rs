@@ -1,9 +1,77 @@
 use wordler::{
-    oracle::memory_oracle::MemoryOracle, petitioner::human_petitioner::HumanPetitioner, wordle,
+    observer::PrintFeedback,
+    oracle::{adversarial_oracle::AdversarialOracle, memory_oracle::MemoryOracle},
+    petitioner::human_petitioner::HumanPetitioner,
+    record,
+    share::{self, ShareOptions},
+    stats::Stats,
+    wordle, Config, Outcome,
 };
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let answer = wordle::<MemoryOracle, HumanPetitioner>(true)?;
-    println!("you win! ({})", answer);
+    let mut config = Config {
+        max_guesses: Some(6),
+        validate_guesses: true,
+        ..Config::default()
+    };
+    let mut share: Option<ShareOptions> = None;
+    let mut adversarial = false;
+    let mut stats = Stats::load()?;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if config.parse_flag(&arg, &mut args)? {
+            continue;
+        }
+        match arg.as_str() {
+            "--adversarial" => adversarial = true,
+            "--share" => {
+                share.get_or_insert_with(ShareOptions::default);
+            }
+            "--high-contrast" => {
+                share
+                    .get_or_insert_with(ShareOptions::default)
+                    .high_contrast = true
+            }
+            "--stats" => {
+                print!("{}", stats);
+                return Ok(());
+            }
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+
+    let record = if adversarial {
+        wordle::<AdversarialOracle, HumanPetitioner>(&config, &mut [&mut PrintFeedback])?
+    } else {
+        let seed = *config.seed.get_or_insert_with(rand::random);
+        println!("seed: {}", seed);
+        wordle::<MemoryOracle, HumanPetitioner>(&config, &mut [&mut PrintFeedback])?
+    };
+    match &record.outcome {
+        Outcome::Won => {
+            let answer = record.target().unwrap_or_default();
+            println!("you win! ({}, in {} guesses)", answer, record.guesses());
+        }
+        Outcome::Lost { target } => {
+            println!(
+                "you lose! (the word was {})",
+                target.as_deref().unwrap_or("a secret")
+            );
+        }
+        Outcome::Error(err) => return Err(record::describe(err).into()),
+    }
+    if let Some(options) = share {
+        let options = ShareOptions {
+            max_guesses: config.max_guesses,
+            hard_mode: config.hard_mode,
+            ..options
+        };
+        println!("\n{}", share::grid(&record, &options));
+    }
+
+    if !adversarial {
+        stats.record(&record)?;
+        print!("\n{}", stats);
+    }
     Ok(())
 }
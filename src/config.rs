@@ -0,0 +1,112 @@
+//! Settings for a game, from which oracles and petitioners construct themselves.
+
+#[cfg(feature = "wordlist")]
+use crate::wordlist::{List, Normalizer, Source};
+
+/// Mixed into `seed` to derive each player's RNG. The oracle's is zero, so it is seeded with `seed`
+/// itself.
+#[cfg(feature = "rand")]
+const ORACLE_STREAM: u64 = 0;
+#[cfg(feature = "rand")]
+const PETITIONER_STREAM: u64 = 0x5851_f42d_4c95_7f2d;
+
+/// Settings for a game.
+///
+/// Each oracle and petitioner uses the settings which are relevant to it and ignores the rest.
+/// Construct with struct update syntax, e.g. `Config { hard_mode: true, ..Config::default() }`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The number of characters in the target word.
+    ///
+    /// Petitioners are always given the length chosen by the oracle.
+    pub word_length: usize,
+    /// The oracle answers at most this many guesses, if set.
+    pub max_guesses: Option<usize>,
+    /// Each guess must use every hint revealed so far.
+    pub hard_mode: bool,
+    /// The oracle rejects guesses which are not among the allowed words.
+    pub validate_guesses: bool,
+    /// Seeds all random choices, for reproducible games.
+    pub seed: Option<u64>,
+    /// The words from which targets are chosen.
+    #[cfg(feature = "wordlist")]
+    pub answers: Source,
+    /// The words which may be guessed.
+    #[cfg(feature = "wordlist")]
+    pub words: Source,
+    /// How words read from local files are cleaned up. Cached lists were cleaned up already.
+    #[cfg(feature = "wordlist")]
+    pub normalizer: Normalizer,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            word_length: 5,
+            max_guesses: None,
+            hard_mode: false,
+            validate_guesses: false,
+            seed: None,
+            #[cfg(feature = "wordlist")]
+            answers: Source::Cached(List::Answers),
+            #[cfg(feature = "wordlist")]
+            words: Source::Cached(List::Allowed),
+            #[cfg(feature = "wordlist")]
+            normalizer: Normalizer::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Apply a command-line flag which changes these settings, taking its value from `args`.
+    ///
+    /// Returns whether `flag` was one of `--length N`, `--max-guesses N`, `--hard`, `--seed N`,
+    /// `--answers PATH`, `--words PATH`, or a flag accepted by [`Normalizer::parse_flag`].
+    pub fn parse_flag(
+        &mut self,
+        flag: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut value = |what: &str| args.next().ok_or(format!("{} requires {}", flag, what));
+        match flag {
+            "--length" => self.word_length = value("a number")?.parse()?,
+            "--max-guesses" => self.max_guesses = Some(value("a number")?.parse()?),
+            "--hard" => self.hard_mode = true,
+            "--seed" => self.seed = Some(value("a number")?.parse()?),
+            #[cfg(feature = "wordlist")]
+            "--answers" => self.answers = Source::Path(value("a path")?.into()),
+            #[cfg(feature = "wordlist")]
+            "--words" => self.words = Source::Path(value("a path")?.into()),
+            #[cfg(feature = "wordlist")]
+            _ => return Ok(self.normalizer.parse_flag(flag, args)?),
+            #[cfg(not(feature = "wordlist"))]
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// An RNG for the oracle, seeded from `seed` if it is set, or from system entropy if not.
+    #[cfg(feature = "rand")]
+    pub fn oracle_rng(&self) -> rand::rngs::StdRng {
+        self.rng(ORACLE_STREAM)
+    }
+
+    /// An RNG for the petitioner, seeded from `seed` if it is set, or from system entropy if not.
+    ///
+    /// Its stream is independent of [`Config::oracle_rng`], so the petitioner's choices are not
+    /// correlated with the target.
+    #[cfg(feature = "rand")]
+    pub fn petitioner_rng(&self) -> rand::rngs::StdRng {
+        self.rng(PETITIONER_STREAM)
+    }
+
+    #[cfg(feature = "rand")]
+    fn rng(&self, stream: u64) -> rand::rngs::StdRng {
+        use rand::SeedableRng;
+
+        match self.seed {
+            Some(seed) => rand::rngs::StdRng::seed_from_u64(seed ^ stream),
+            None => rand::rngs::StdRng::from_entropy(),
+        }
+    }
+}
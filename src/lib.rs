@@ -1,13 +1,24 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod config;
+pub mod observer;
 pub mod oracle;
 pub mod petitioner;
+pub mod record;
+pub mod share;
+#[cfg(feature = "stats")]
+pub mod stats;
 #[cfg(feature = "wordlist")]
 pub mod wordlist;
 
+pub use config::Config;
+pub use observer::Observer;
 pub use oracle::Oracle;
 pub use petitioner::Petitioner;
+pub use record::{GameError, GameRecord, Outcome, Turn};
 
 #[cfg(feature = "pretty_feedback")]
-pub fn print_feedback(guess: &str, feedback: &crate::oracle::FeedbackRef) {
+pub fn print_feedback(guess: &str, feedback: &crate::oracle::Feedback) {
     use crate::oracle::Disposition::*;
     use itertools::Itertools;
     use std::io::Write;
@@ -75,65 +86,134 @@ pub fn print_feedback(guess: &str, feedback: &crate::oracle::FeedbackRef) {
 }
 
 #[cfg(not(feature = "pretty_feedback"))]
-pub fn print_feedback(guess: &str, feedback: &crate::oracle::FeedbackRef) {
-    println!("guess: {}", guess);
-    println!("feedback: {:?}", feedback);
+pub fn print_feedback(guess: &str, feedback: &crate::oracle::Feedback) {
+    println!("guess:    {}", guess);
+    println!("feedback: {}", feedback);
 }
 
-/// Run a game of wordle according to the oracle and petitioner.
-pub fn wordle<Oracle, Petitioner>(show_feedback: bool) -> Result<String, Box<dyn std::error::Error>>
+/// Run a game of wordle according to the oracle and petitioner, each constructed from `config`.
+///
+/// Errors constructing either are returned directly; errors during the game are recorded in the
+/// outcome. Each of `observers` is notified of events as the game is played.
+///
+/// If `config.seed` is not set, a seed is chosen at random. Either way, it is recorded, so that
+/// the game can be replayed exactly by setting it.
+pub fn wordle<Oracle, Petitioner>(
+    config: &Config,
+    observers: &mut [&mut dyn Observer],
+) -> Result<GameRecord, Box<dyn std::error::Error>>
 where
-    Oracle: 'static + oracle::Oracle,
-    Petitioner: 'static + petitioner::Petitioner,
+    Oracle: oracle::Oracle,
+    Petitioner: petitioner::Petitioner,
 {
-    wordle_config(show_feedback, |_: &mut Oracle| {}, |_: &mut Petitioner| {})
+    let mut config = config.clone();
+    #[cfg(feature = "rand")]
+    config.seed.get_or_insert_with(rand::random);
+
+    let oracle = Oracle::new(&config)?;
+    config.word_length = oracle.word_length()?;
+    let mut petitioner = Petitioner::new(&config)?;
+
+    Ok(GameRecord {
+        seed: config.seed,
+        ..play(&*oracle, &mut *petitioner, observers)
+    })
 }
 
-/// Run a game of wordle according to the oracle and petitioner.
+/// Run a game of wordle between an oracle and a petitioner which have already been constructed.
 ///
-/// The callback functions are each invoked once, after the relevant struct is initialized but before
-/// it is used.
-pub fn wordle_config<Oracle, Petitioner, AdjustOracle, AdjustPetitioner>(
-    show_feedback: bool,
-    adjust_oracle: AdjustOracle,
-    adjust_petitioner: AdjustPetitioner,
-) -> Result<String, Box<dyn std::error::Error>>
+/// Each of `observers` is notified of events as the game is played. The record has no seed, as the
+/// players' randomness is out of the runner's hands.
+pub fn play<Oracle, Petitioner>(
+    oracle: &Oracle,
+    petitioner: &mut Petitioner,
+    observers: &mut [&mut dyn Observer],
+) -> GameRecord
 where
-    Oracle: 'static + oracle::Oracle,
-    Petitioner: 'static + petitioner::Petitioner,
-    AdjustOracle: FnOnce(&mut Oracle),
-    AdjustPetitioner: FnOnce(&mut Petitioner),
+    Oracle: oracle::Oracle + ?Sized,
+    Petitioner: petitioner::Petitioner + ?Sized,
 {
-    use std::any::Any;
+    let start = std::time::Instant::now();
+    let mut turns = Vec::new();
 
-    let oracle = {
-        let mut oracle = Oracle::new()?;
-        let oracle_any = &mut *oracle as &mut dyn Any;
-        adjust_oracle(oracle_any.downcast_mut().expect("type has to work here"));
-        oracle
+    let outcome = match oracle.word_length() {
+        Ok(word_length) => {
+            for observer in observers.iter_mut() {
+                observer.game_started(word_length);
+            }
+            play_turns(oracle, petitioner, observers, &mut turns)
+        }
+        Err(err) => Outcome::Error(err.into()),
     };
 
-    let word_length = oracle.word_length()?;
-    let mut petitioner = Petitioner::new(word_length)?;
-    let petitioner_any = &mut *petitioner as &mut dyn Any;
-    adjust_petitioner(
-        petitioner_any
-            .downcast_mut()
-            .expect("type has to work here"),
-    );
+    GameRecord {
+        turns,
+        outcome,
+        duration: start.elapsed(),
+        seed: None,
+    }
+}
+
+/// Play turns until the game ends, recording each judged guess in `turns`.
+fn play_turns<Oracle, Petitioner>(
+    oracle: &Oracle,
+    petitioner: &mut Petitioner,
+    observers: &mut [&mut dyn Observer],
+    turns: &mut Vec<Turn>,
+) -> Outcome
+where
+    Oracle: oracle::Oracle + ?Sized,
+    Petitioner: petitioner::Petitioner + ?Sized,
+{
+    let lost = |observers: &mut [&mut dyn Observer]| {
+        let target = oracle.reveal();
+        for observer in observers.iter_mut() {
+            observer.lost(target.as_deref());
+        }
+        Outcome::Lost { target }
+    };
 
     loop {
-        let guess = petitioner.prepare_guess()?;
-        match oracle.guess(&guess)? {
-            Ok(_) => {
-                return Ok(guess);
+        let guess = match petitioner.prepare_guess() {
+            Ok(guess) => guess,
+            Err(err) => return Outcome::Error(err.into()),
+        };
+        for observer in observers.iter_mut() {
+            observer.guess_submitted(&guess);
+        }
+        match oracle.guess(&guess) {
+            Ok(Ok(_)) => {
+                for observer in observers.iter_mut() {
+                    observer.won(&guess, turns.len() + 1);
+                }
+                turns.push(Turn::winning(guess));
+                return Outcome::Won;
             }
-            Err(feedback) => {
-                if show_feedback {
-                    print_feedback(&guess, &feedback);
+            Ok(Err(feedback)) => {
+                for observer in observers.iter_mut() {
+                    observer.feedback_received(&guess, &feedback);
+                }
+                turns.push(Turn {
+                    guess,
+                    feedback: feedback.clone(),
+                });
+                if oracle.guesses_remaining() == Some(0) {
+                    return lost(observers);
+                }
+                if let Err(err) = petitioner.feedback(feedback) {
+                    return Outcome::Error(err.into());
+                }
+            }
+            Err(err) if err.is_rejection() => {
+                for observer in observers.iter_mut() {
+                    observer.guess_rejected(&guess, &err);
+                }
+                if let Err(err) = petitioner.rejected(&guess, err) {
+                    return Outcome::Error(err.into());
                 }
-                petitioner.feedback(feedback)?;
             }
+            Err(oracle::Error::TooManyGuesses) => return lost(observers),
+            Err(err) => return Outcome::Error(err.into()),
         }
     }
 }
@@ -0,0 +1,37 @@
+//! Hooks for following a game as it is played.
+
+use crate::oracle::{self, Feedback};
+
+/// Notified of each event in a game.
+///
+/// Every method does nothing by default, so implementors need only handle the events they care
+/// about.
+pub trait Observer {
+    /// The game is about to begin, with a target of the given length.
+    fn game_started(&mut self, _word_length: usize) {}
+
+    /// The petitioner submitted a guess to the oracle.
+    fn guess_submitted(&mut self, _guess: &str) {}
+
+    /// The oracle refused to judge a guess, which does not count against the petitioner.
+    fn guess_rejected(&mut self, _guess: &str, _reason: &oracle::Error) {}
+
+    /// The oracle judged an incorrect guess.
+    fn feedback_received(&mut self, _guess: &str, _feedback: &Feedback) {}
+
+    /// The petitioner guessed the target, after the given number of judged guesses.
+    fn won(&mut self, _target: &str, _guesses: usize) {}
+
+    /// The petitioner ran out of guesses. The target is given if the oracle revealed it.
+    fn lost(&mut self, _target: Option<&str>) {}
+}
+
+/// Prints the feedback for each incorrect guess with [`print_feedback`](crate::print_feedback).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PrintFeedback;
+
+impl Observer for PrintFeedback {
+    fn feedback_received(&mut self, guess: &str, feedback: &Feedback) {
+        crate::print_feedback(guess, feedback);
+    }
+}
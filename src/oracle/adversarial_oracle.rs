@@ -0,0 +1,164 @@
+use std::{cell::RefCell, cmp::Reverse, collections::HashMap};
+
+use crate::oracle::{score, Disposition, Error, Feedback, Oracle, Rules};
+
+/// An oracle which never commits to a target.
+///
+/// It keeps every answer consistent with the feedback so far. For each guess it gives whichever
+/// feedback keeps the most of them, so the guess is only correct once it is the last word left.
+pub struct AdversarialOracle {
+    word_length: usize,
+    /// every answer consistent with the feedback so far, in their original order
+    remaining: RefCell<Vec<String>>,
+    /// The guess limit, hard mode, and allowed words; the remaining answers are always allowed.
+    pub rules: Rules,
+}
+
+impl Oracle for AdversarialOracle {
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error> {
+        let words = crate::wordlist::answers_with(
+            &config.answers,
+            &config.words,
+            config.word_length,
+            &config.normalizer,
+        )
+        .map_err(|err| Error::Io(Box::new(err)))?;
+        let mut oracle = Self::with_words(config.word_length, words)?;
+        oracle.rules = Rules::from_config(config)?;
+        Ok(oracle)
+    }
+
+    fn word_length(&self) -> Result<usize, Error> {
+        Ok(self.word_length)
+    }
+
+    fn guess(&self, guess: &str) -> Result<Result<(), Feedback>, Error> {
+        let possible_target = self.remaining.borrow().iter().any(|word| word == guess);
+        self.rules.admit(guess, possible_target)?;
+
+        let mut remaining = self.remaining.borrow_mut();
+        let (fb, words) = largest_bucket(guess, &remaining);
+        *remaining = words;
+        if fb.is_correct() {
+            Ok(Ok(()))
+        } else {
+            self.rules.record(guess, &fb);
+            Ok(Err(fb))
+        }
+    }
+
+    fn guesses_remaining(&self) -> Option<usize> {
+        self.rules.guesses_remaining()
+    }
+
+    /// Any of the answers which are still consistent with every guess.
+    fn reveal(&self) -> Option<String> {
+        self.remaining.borrow().first().cloned()
+    }
+}
+
+impl AdversarialOracle {
+    /// Create an oracle which may choose any of these words, each of `word_length` characters.
+    pub fn with_words(word_length: usize, words: Vec<String>) -> Result<Box<Self>, Error> {
+        if words.is_empty() {
+            return Err(Error::NoWords);
+        }
+        debug_assert!(words.iter().all(|word| word.chars().count() == word_length));
+        Ok(Box::new(Self {
+            word_length,
+            remaining: RefCell::new(words),
+            rules: Rules::default(),
+        }))
+    }
+
+    /// The answers which are still consistent with every guess.
+    pub fn remaining(&self) -> usize {
+        self.remaining.borrow().len()
+    }
+}
+
+/// Partition `words` by the feedback each would give to `guess`, and pick the feedback to give.
+///
+/// The largest partition wins. Ties go to the feedback which reveals least, so a correct guess is
+/// only ever conceded when no other word remains. Any further tie goes to the partition containing
+/// the earliest word, so the choice is deterministic.
+fn largest_bucket(guess: &str, words: &[String]) -> (Feedback, Vec<String>) {
+    // feedback -> (index of its earliest word, its words)
+    let mut buckets: HashMap<Feedback, (usize, Vec<String>)> = HashMap::new();
+    for (idx, word) in words.iter().enumerate() {
+        buckets
+            .entry(score(guess, word))
+            .or_insert_with(|| (idx, Vec::new()))
+            .1
+            .push(word.clone());
+    }
+    buckets
+        .into_iter()
+        .max_by_key(|(fb, (first, words))| {
+            (
+                words.len(),
+                !fb.is_correct(),
+                Reverse(revealed(fb)),
+                Reverse(*first),
+            )
+        })
+        .map(|(fb, (_, words))| (fb, words))
+        .expect("there is always at least one remaining word")
+}
+
+/// How much a piece of feedback gives away: two points per correct letter, one per misplaced.
+fn revealed(fb: &Feedback) -> usize {
+    fb.iter()
+        .map(|disposition| match disposition {
+            Disposition::Correct => 2,
+            Disposition::WrongPosition => 1,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle(words: &[&str]) -> Box<AdversarialOracle> {
+        let words = words.iter().map(|&word| word.to_owned()).collect();
+        AdversarialOracle::with_words(3, words).expect("words were given")
+    }
+
+    #[test]
+    fn concedes_only_the_last_word() {
+        let oracle = oracle(&["abc", "abd", "abe"]);
+
+        assert_eq!(oracle.guess("abc").unwrap(), Err("GGB".parse().unwrap()));
+        assert_eq!(oracle.remaining(), 2);
+        // both remaining words would leave one, so it denies the guess
+        assert_eq!(oracle.guess("abd").unwrap(), Err("GGB".parse().unwrap()));
+        assert_eq!(oracle.remaining(), 1);
+        assert_eq!(oracle.reveal().as_deref(), Some("abe"));
+        assert_eq!(oracle.guess("abe").unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn keeps_the_largest_bucket() {
+        let oracle = oracle(&["abc", "xyz", "xyw", "xzz"]);
+
+        // "xyz" splits these into {abc}, {xyz}, {xyw}, {xzz}; all ties, so it reveals least
+        assert_eq!(oracle.guess("xyz").unwrap(), Err("BBB".parse().unwrap()));
+        assert_eq!(oracle.remaining(), 1);
+        assert_eq!(oracle.guess("abc").unwrap(), Ok(()));
+    }
+
+    #[test]
+    fn enforces_rules() {
+        let mut oracle = oracle(&["abc", "abd", "abe"]);
+        oracle.rules.max_guesses = Some(2);
+        oracle.rules.hard_mode = true;
+
+        assert_eq!(oracle.guess("abc").unwrap(), Err("GGB".parse().unwrap()));
+        assert!(matches!(oracle.guess("xyz"), Err(Error::HardMode(_))));
+        assert_eq!(oracle.guesses_remaining(), Some(1));
+        assert_eq!(oracle.guess("abd").unwrap(), Err("GGB".parse().unwrap()));
+        assert!(matches!(oracle.guess("abe"), Err(Error::TooManyGuesses)));
+    }
+}
@@ -0,0 +1,315 @@
+//! The oracle's judgement of a guess, and its textual forms.
+//!
+//! Feedback is written one character per letter, either as letter codes (`G` correct, `Y` wrong
+//! position, `B` not in word) or as emoji (🟩, 🟨, ⬛). In both forms a missing letter is written
+//! `_` and an extra letter `+`. Parsing accepts either form, in any mix, ignoring whitespace.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+/// The disposition of a letter indicates how guessers should refine their list of potential words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Disposition {
+    /// This letter does not appear in the target word.
+    NotInWord,
+    /// This letter appears in the target word elsewhere, more times than it has been matched
+    /// by `Correct` or earlier `WrongPosition` letters in this guess.
+    WrongPosition,
+    /// This letter appears in this position in the target word.
+    Correct,
+    /// The guess was shorter than the target word; this letter is missing.
+    Missing,
+    /// The guess was longer than the target workd; this letter is extra (and unchecked) otherwise.
+    Extra,
+}
+
+impl Disposition {
+    /// The letter code for this disposition: `G`, `Y`, `B`, or a missing or extra marker.
+    pub fn letter(self) -> char {
+        match self {
+            Disposition::NotInWord => 'B',
+            Disposition::WrongPosition => 'Y',
+            Disposition::Correct => 'G',
+            Disposition::Missing => '_',
+            Disposition::Extra => '+',
+        }
+    }
+
+    /// The emoji for this disposition: 🟩, 🟨, ⬛, or a missing or extra marker.
+    pub fn emoji(self) -> char {
+        match self {
+            Disposition::NotInWord => '⬛',
+            Disposition::WrongPosition => '🟨',
+            Disposition::Correct => '🟩',
+            Disposition::Missing => '_',
+            Disposition::Extra => '+',
+        }
+    }
+}
+
+impl TryFrom<char> for Disposition {
+    type Error = ParseFeedbackError;
+
+    /// Accepts letter codes in either case, and `.` as well as `B` for letters not in the word.
+    /// Accepts ⬜ as well as ⬛, and the high-contrast 🟧 and 🟦 for 🟩 and 🟨.
+    fn try_from(character: char) -> Result<Self, Self::Error> {
+        match character {
+            'b' | 'B' | '.' | '⬛' | '⬜' => Ok(Disposition::NotInWord),
+            'y' | 'Y' | '🟨' | '🟦' => Ok(Disposition::WrongPosition),
+            'g' | 'G' | '🟩' | '🟧' => Ok(Disposition::Correct),
+            '_' => Ok(Disposition::Missing),
+            '+' => Ok(Disposition::Extra),
+            _ => Err(ParseFeedbackError::UnrecognizedCharacter {
+                character,
+                position: None,
+            }),
+        }
+    }
+}
+
+/// Writes the letter code, or the emoji with the alternate flag (`{:#}`).
+impl fmt::Display for Disposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.emoji())
+        } else {
+            write!(f, "{}", self.letter())
+        }
+    }
+}
+
+impl FromStr for Disposition {
+    type Err = ParseFeedbackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let feedback: Feedback = s.parse()?;
+        match feedback.as_slice() {
+            [disposition] => Ok(*disposition),
+            _ => Err(ParseFeedbackError::WrongLength {
+                expected: 1,
+                found: feedback.len(),
+            }),
+        }
+    }
+}
+
+/// The disposition of each letter of a guess, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Feedback(pub Vec<Disposition>);
+
+pub type FeedbackRef = [Disposition];
+
+impl Feedback {
+    /// Whether every letter is `Correct`, i.e. the guess was the target.
+    pub fn is_correct(&self) -> bool {
+        self.iter()
+            .all(|&disposition| disposition == Disposition::Correct)
+    }
+
+    /// Feedback for a correct guess of `length` letters.
+    pub fn correct(length: usize) -> Self {
+        Feedback(vec![Disposition::Correct; length])
+    }
+}
+
+impl Deref for Feedback {
+    type Target = Vec<Disposition>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Feedback {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<Disposition>> for Feedback {
+    fn from(dispositions: Vec<Disposition>) -> Self {
+        Feedback(dispositions)
+    }
+}
+
+impl From<Feedback> for Vec<Disposition> {
+    fn from(feedback: Feedback) -> Self {
+        feedback.0
+    }
+}
+
+impl FromIterator<Disposition> for Feedback {
+    fn from_iter<I: IntoIterator<Item = Disposition>>(iter: I) -> Self {
+        Feedback(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Feedback {
+    type Item = Disposition;
+    type IntoIter = std::vec::IntoIter<Disposition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Feedback {
+    type Item = &'a Disposition;
+    type IntoIter = std::slice::Iter<'a, Disposition>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Writes letter codes such as `GYBBG`, or emoji with the alternate flag (`{:#}`).
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for disposition in self.iter() {
+            fmt::Display::fmt(disposition, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = ParseFeedbackError;
+
+    /// Parses letter codes, emoji, or a mix, ignoring whitespace and emoji variation selectors.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let feedback: Feedback = s
+            .chars()
+            .filter(|&character| !character.is_whitespace() && character != '\u{fe0f}')
+            .enumerate()
+            .map(|(position, character)| {
+                Disposition::try_from(character).map_err(|_| {
+                    ParseFeedbackError::UnrecognizedCharacter {
+                        character,
+                        position: Some(position),
+                    }
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        if feedback.is_empty() {
+            return Err(ParseFeedbackError::Empty);
+        }
+        Ok(feedback)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseFeedbackError {
+    #[error("no feedback was given")]
+    Empty,
+    /// `position` counts from zero, ignoring whitespace, when the character was part of a string.
+    #[error(
+        "unrecognized feedback {character:?}{}",
+        .position.map(|position| format!(" for letter {}", position + 1)).unwrap_or_default()
+    )]
+    UnrecognizedCharacter {
+        character: char,
+        position: Option<usize>,
+    },
+    #[error("expected feedback for {expected} letters but found {found}")]
+    WrongLength { expected: usize, found: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Disposition::*;
+
+    const ALL: [Disposition; 5] = [NotInWord, WrongPosition, Correct, Missing, Extra];
+
+    #[test]
+    fn disposition_round_trips() {
+        for disposition in ALL {
+            assert_eq!(disposition.to_string().parse(), Ok(disposition));
+            assert_eq!(format!("{:#}", disposition).parse(), Ok(disposition));
+            assert_eq!(Disposition::try_from(disposition.letter()), Ok(disposition));
+            assert_eq!(Disposition::try_from(disposition.emoji()), Ok(disposition));
+        }
+    }
+
+    #[test]
+    fn feedback_round_trips() {
+        let feedback = Feedback(vec![
+            Correct,
+            WrongPosition,
+            NotInWord,
+            NotInWord,
+            Correct,
+            Missing,
+        ]);
+        assert_eq!(feedback.to_string(), "GYBBG_");
+        assert_eq!(format!("{:#}", feedback), "🟩🟨⬛⬛🟩_");
+        assert_eq!(feedback.to_string().parse(), Ok(feedback.clone()));
+        assert_eq!(format!("{:#}", feedback).parse(), Ok(feedback));
+
+        let extra = Feedback(vec![Correct, Extra, Extra]);
+        assert_eq!(extra.to_string().parse(), Ok(extra.clone()));
+        assert_eq!(format!("{:#}", extra).parse(), Ok(extra));
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        let expected = Ok(Feedback(vec![
+            Correct,
+            WrongPosition,
+            NotInWord,
+            NotInWord,
+            Correct,
+        ]));
+        assert_eq!("gy..g".parse(), expected);
+        assert_eq!("G Y b B g".parse(), expected);
+        assert_eq!("🟩🟨⬜⬜🟩".parse(), expected);
+        assert_eq!("🟧🟦⬛\u{fe0f}⬛\u{fe0f}🟧".parse(), expected);
+        assert_eq!("g🟨.⬛G".parse(), expected);
+    }
+
+    #[test]
+    fn typed_errors() {
+        assert_eq!("".parse::<Feedback>(), Err(ParseFeedbackError::Empty));
+        assert_eq!(" \t".parse::<Feedback>(), Err(ParseFeedbackError::Empty));
+        assert_eq!(
+            "gyx".parse::<Feedback>(),
+            Err(ParseFeedbackError::UnrecognizedCharacter {
+                character: 'x',
+                position: Some(2)
+            })
+        );
+        assert_eq!(
+            Disposition::try_from('x'),
+            Err(ParseFeedbackError::UnrecognizedCharacter {
+                character: 'x',
+                position: None
+            })
+        );
+        assert_eq!(
+            "gy".parse::<Disposition>(),
+            Err(ParseFeedbackError::WrongLength {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            ParseFeedbackError::UnrecognizedCharacter {
+                character: 'x',
+                position: Some(2)
+            }
+            .to_string(),
+            "unrecognized feedback 'x' for letter 3"
+        );
+        assert_eq!(
+            ParseFeedbackError::UnrecognizedCharacter {
+                character: 'x',
+                position: None
+            }
+            .to_string(),
+            "unrecognized feedback 'x'"
+        );
+    }
+}
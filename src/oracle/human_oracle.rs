@@ -0,0 +1,117 @@
+use std::io::{self, BufRead, Write};
+
+use crate::oracle::{Disposition, Error, Feedback, Oracle, ParseFeedbackError, Rules};
+
+/// An oracle for a word which a human is thinking of.
+///
+/// Each guess is shown at the terminal, and the human types back the colors it earned.
+#[derive(Debug)]
+pub struct HumanOracle {
+    word_length: usize,
+    /// The guess limit and hard mode; the human decides which guesses are words.
+    pub rules: Rules,
+}
+
+impl Oracle for HumanOracle {
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error> {
+        let word_length = loop {
+            let answer = prompt(&format!(
+                "How many letters are in your word? [{}] ",
+                config.word_length
+            ))?;
+            if answer.is_empty() {
+                break config.word_length;
+            }
+            match answer.parse() {
+                Ok(word_length) if word_length > 0 => break word_length,
+                _ => println!("please enter a positive number"),
+            }
+        };
+        println!("For each guess, enter one character per letter:");
+        println!("  g or 🟩: correct");
+        println!("  y or 🟨: in the word, but elsewhere");
+        println!("  b, . or ⬛: not in the word");
+        println!("or ! if the guess is not a word.");
+        Ok(Box::new(Self {
+            word_length,
+            rules: Rules {
+                max_guesses: config.max_guesses,
+                hard_mode: config.hard_mode,
+                ..Rules::default()
+            },
+        }))
+    }
+
+    fn word_length(&self) -> Result<usize, Error> {
+        Ok(self.word_length)
+    }
+
+    fn guess(&self, guess: &str) -> Result<Result<(), Feedback>, Error> {
+        if guess.chars().count() != self.word_length {
+            return Err(Error::NotAWord(guess.to_owned()));
+        }
+        self.rules.admit(guess, true)?;
+
+        println!("guess: {}", guess);
+        let feedback = loop {
+            let answer = prompt("> ")?;
+            if answer == "!" {
+                self.rules.withdraw();
+                return Err(Error::NotAWord(guess.to_owned()));
+            }
+            match answer.parse::<Feedback>() {
+                Ok(feedback)
+                    if feedback
+                        .iter()
+                        .any(|disp| matches!(disp, Disposition::Missing | Disposition::Extra)) =>
+                {
+                    println!("colors must be green, yellow or grey")
+                }
+                Ok(feedback) if feedback.len() == self.word_length => break feedback,
+                Ok(feedback) => println!(
+                    "{}",
+                    ParseFeedbackError::WrongLength {
+                        expected: self.word_length,
+                        found: feedback.len(),
+                    }
+                ),
+                Err(err) => println!("{}", err),
+            }
+        };
+
+        if feedback.is_correct() {
+            Ok(Ok(()))
+        } else {
+            self.rules.record(guess, &feedback);
+            Ok(Err(feedback))
+        }
+    }
+
+    fn guesses_remaining(&self) -> Option<usize> {
+        self.rules.guesses_remaining()
+    }
+
+    fn reveal(&self) -> Option<String> {
+        let answer = prompt("What was your word? ").ok()?;
+        (!answer.is_empty()).then_some(answer)
+    }
+}
+
+/// Print `question` and read a trimmed line in reply.
+fn prompt(question: &str) -> Result<String, Error> {
+    let mut stdout = io::stdout();
+    write!(stdout, "{}", question).map_err(|err| Error::Io(Box::new(err)))?;
+    stdout.flush().map_err(|err| Error::Io(Box::new(err)))?;
+
+    let mut line = String::new();
+    let read = io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|err| Error::Io(Box::new(err)))?;
+    if read == 0 {
+        return Err(Error::Io(Box::new(io::Error::from(
+            io::ErrorKind::UnexpectedEof,
+        ))));
+    }
+    Ok(line.trim().to_owned())
+}
@@ -1,20 +1,27 @@
-use std::cell::Cell;
-
-use crate::oracle::{Disposition, Error, Feedback, Oracle};
-use rand::seq::IteratorRandom;
+use crate::oracle::{score, Error, Feedback, Oracle, Rules};
+use crate::wordlist::{List, Source};
+use rand::seq::SliceRandom;
 
 // This implementation feels dumb, but for words on the order of 5 chars long, this may actually
 // be more efficient than anything more complicated.
 pub struct MemoryOracle {
     target: String,
-    guesses: Cell<usize>,
-    pub max_guesses: Option<usize>,
+    /// The guess limit, hard mode, and allowed words; the target is always allowed.
+    pub rules: Rules,
 }
 
 impl Oracle for MemoryOracle {
-    fn new() -> Result<Box<Self>, Error> {
-        // by default we pick a 5-character word
-        Self::create_random(5)
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error> {
+        let words = crate::wordlist::answers_with(
+            &config.answers,
+            &config.words,
+            config.word_length,
+            &config.normalizer,
+        )
+        .map_err(|err| Error::Io(Box::new(err)))?;
+        let mut oracle = Self::random_from_with_rng(&words, &mut config.oracle_rng())?;
+        oracle.rules = Rules::from_config(config)?;
+        Ok(oracle)
     }
 
     fn word_length(&self) -> Result<usize, Error> {
@@ -22,64 +29,84 @@ impl Oracle for MemoryOracle {
     }
 
     fn guess(&self, guess: &str) -> Result<Result<(), Feedback>, Error> {
-        let mut guesses = self.guesses.get();
-        guesses += 1;
-        self.guesses.set(guesses);
-
-        if self
-            .max_guesses
-            .map(|max_guesses| guesses > max_guesses)
-            .unwrap_or_default()
-        {
-            Err(Error::TooManyGuesses)
-        } else if guess == self.target {
+        self.rules.admit(guess, guess == self.target)?;
+        if guess == self.target {
             Ok(Ok(()))
         } else {
-            let mut fb = Feedback::with_capacity(guess.len());
-            for (have, want) in guess.chars().zip(self.target.chars()) {
-                if have == want {
-                    fb.push(Disposition::Correct);
-                } else if self.target.contains(have) {
-                    fb.push(Disposition::WrongPosition);
-                } else {
-                    fb.push(Disposition::NotInWord);
-                }
-            }
-
-            let target_chars = self.target.chars().count();
-            let guess_chars = guess.chars().count();
-            fb.extend(
-                std::iter::repeat(Disposition::Missing)
-                    .take(target_chars.saturating_sub(guess_chars)),
-            );
-            fb.extend(
-                std::iter::repeat(Disposition::Extra)
-                    .take(guess_chars.saturating_sub(target_chars)),
-            );
-
-            debug_assert!(fb.len() >= target_chars);
-            debug_assert!(fb.len() <= guess_chars);
-            debug_assert!(!fb.iter().all(|&disp| disp == Disposition::Correct));
-
+            let fb = score(guess, &self.target);
+            debug_assert!(!fb.is_correct());
+            self.rules.record(guess, &fb);
             Ok(Err(fb))
         }
     }
+
+    fn guesses_remaining(&self) -> Option<usize> {
+        self.rules.guesses_remaining()
+    }
+
+    fn reveal(&self) -> Option<String> {
+        Some(self.target.clone())
+    }
 }
 
 impl MemoryOracle {
-    fn create_random(characters: usize) -> Result<Box<Self>, Error> {
-        let mut rng = rand::thread_rng();
+    /// Create an oracle with a known target.
+    pub fn with_target(target: String) -> Box<Self> {
+        Box::new(Self {
+            target,
+            rules: Rules::default(),
+        })
+    }
 
-        let word = crate::wordlist::load()
-            .map_err(|err| Error::Io(Box::new(err)))?
-            .filter(|word| word.chars().count() == characters)
-            .choose(&mut rng)
-            .expect("word list was empty");
+    /// Create an oracle whose target is a random answer of the given length.
+    ///
+    /// Answers are drawn from the standard cached lists; see [`MemoryOracle::from_sources`].
+    pub fn random(characters: usize) -> Result<Box<Self>, Error> {
+        Self::from_sources(characters, &List::Answers.into(), &List::Allowed.into())
+    }
+
+    /// Create an oracle whose target is a random word from `words`.
+    pub fn random_from(words: &[String]) -> Result<Box<Self>, Error> {
+        Self::random_from_with_rng(words, &mut rand::thread_rng())
+    }
+
+    /// As [`MemoryOracle::random_from`], but choose the target with the supplied RNG.
+    pub fn random_from_with_rng<Rng: rand::Rng + ?Sized>(
+        words: &[String],
+        rng: &mut Rng,
+    ) -> Result<Box<Self>, Error> {
+        let word = words.choose(rng).ok_or(Error::NoWords)?;
+        Ok(Self::with_target(word.clone()))
+    }
+
+    /// Create an oracle whose target is a random word of the given length from `answers`.
+    ///
+    /// Only answers which also appear in `allowed` are considered, so that a solver drawing its
+    /// guesses from `allowed` can always find the target.
+    pub fn from_sources(
+        characters: usize,
+        answers: &Source,
+        allowed: &Source,
+    ) -> Result<Box<Self>, Error> {
+        Self::from_sources_with_rng(characters, answers, allowed, &mut rand::thread_rng())
+    }
+
+    /// As [`MemoryOracle::from_sources`], but choose the target with the supplied RNG.
+    ///
+    /// With a seeded RNG, the same target is chosen every time.
+    pub fn from_sources_with_rng<Rng: rand::Rng + ?Sized>(
+        characters: usize,
+        answers: &Source,
+        allowed: &Source,
+        rng: &mut Rng,
+    ) -> Result<Box<Self>, Error> {
+        let words = crate::wordlist::answers(answers, allowed, characters)
+            .map_err(|err| Error::Io(Box::new(err)))?;
+        Self::random_from_with_rng(&words, rng)
+    }
 
-        Ok(Box::new(Self {
-            target: word,
-            max_guesses: None,
-            guesses: Cell::new(0),
-        }))
+    /// The number of guesses judged so far, not counting rejected guesses.
+    pub fn guesses(&self) -> usize {
+        self.rules.guesses()
     }
 }
@@ -1,41 +1,442 @@
+#[cfg(feature = "adversarial_oracle")]
+pub mod adversarial_oracle;
+pub mod feedback;
+#[cfg(feature = "human_oracle")]
+pub mod human_oracle;
 #[cfg(feature = "memory_oracle")]
 pub mod memory_oracle;
 
+pub use feedback::{Disposition, Feedback, FeedbackRef, ParseFeedbackError};
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+};
+
 /// An Oracle knows a secret word and is willing to give feedback regarding the
 /// nature of the word.
 pub trait Oracle {
-    /// Create and initialize the oracle.
-    fn new() -> Result<Box<Self>, Error>;
+    /// Create and initialize the oracle according to the game settings.
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error>
+    where
+        Self: Sized;
 
     /// Return the number of characters in the target word.
     fn word_length(&self) -> Result<usize, Error>;
 
     /// Return whether a word was correct, or feedback if it was not.
+    ///
+    /// Errors for which [`Error::is_rejection`] holds do not use up a guess.
     fn guess(&self, guess: &str) -> Result<Result<(), Feedback>, Error>;
+
+    /// Return how many more guesses the oracle will judge, if it is limited.
+    fn guesses_remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Return the target word, if the oracle is willing to say.
+    ///
+    /// This is consulted once a game is over without the target having been guessed.
+    fn reveal(&self) -> Option<String> {
+        None
+    }
 }
 
-/// The disposition of a letter indicates how guessers should refine their list of potential words.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Disposition {
-    /// This letter does not appear in the target word.
-    NotInWord,
-    /// This letter appears at least once in the target word, elsewhere.
-    WrongPosition,
-    /// This letter appears in this position in the target word.
-    Correct,
-    /// The guess was shorter than the target word; this letter is missing.
-    Missing,
-    /// The guess was longer than the target workd; this letter is extra (and unchecked) otherwise.
-    Extra,
+/// Compute the feedback for a guess against a target word.
+///
+/// Letters are counted, as in the real game: a letter which appears in the target is marked
+/// `WrongPosition` only as many times as it remains in the target after all `Correct` letters
+/// have been assigned. Any further copies are marked `NotInWord`.
+pub fn score(guess: &str, target: &str) -> Feedback {
+    let guess: Vec<char> = guess.chars().collect();
+    let target: Vec<char> = target.chars().collect();
+
+    let mut fb = vec![Disposition::NotInWord; guess.len().min(target.len())];
+    // target letters not already accounted for by a correct guess letter
+    let mut unmatched = Vec::with_capacity(target.len());
+    for (idx, (have, want)) in guess.iter().zip(target.iter()).enumerate() {
+        if have == want {
+            fb[idx] = Disposition::Correct;
+        } else {
+            unmatched.push(*want);
+        }
+    }
+    // target letters past the end of a short guess can still be matched out of position
+    unmatched.extend(target.iter().skip(guess.len()).copied());
+    for (idx, have) in guess.iter().take(fb.len()).enumerate() {
+        if fb[idx] == Disposition::Correct {
+            continue;
+        }
+        if let Some(pos) = unmatched.iter().position(|want| want == have) {
+            unmatched.swap_remove(pos);
+            fb[idx] = Disposition::WrongPosition;
+        }
+    }
+
+    fb.extend(std::iter::repeat_n(
+        Disposition::Missing,
+        target.len().saturating_sub(guess.len()),
+    ));
+    fb.extend(std::iter::repeat_n(
+        Disposition::Extra,
+        guess.len().saturating_sub(target.len()),
+    ));
+
+    debug_assert_eq!(fb.len(), guess.len().max(target.len()));
+    Feedback(fb)
 }
 
-pub type Feedback = Vec<Disposition>;
-pub type FeedbackRef = [Disposition];
+/// Check that a guess uses every hint revealed by the feedback on an earlier guess.
+///
+/// Under hard mode, each letter marked `Correct` must be reused in the same position, and each
+/// letter marked `Correct` or `WrongPosition` must appear in the guess at least as many times as it
+/// was so marked.
+pub fn check_hard_mode(
+    guess: &str,
+    previous_guess: &str,
+    previous_feedback: &FeedbackRef,
+) -> Result<(), HardModeViolation> {
+    let guess: Vec<char> = guess.chars().collect();
+
+    let mut required: Vec<(char, usize)> = Vec::new();
+    for (position, (letter, disposition)) in previous_guess
+        .chars()
+        .zip(previous_feedback.iter().copied())
+        .enumerate()
+    {
+        if !matches!(
+            disposition,
+            Disposition::Correct | Disposition::WrongPosition
+        ) {
+            continue;
+        }
+        if disposition == Disposition::Correct && guess.get(position) != Some(&letter) {
+            return Err(HardModeViolation::MustBeAt { position, letter });
+        }
+        match required.iter_mut().find(|(ch, _)| *ch == letter) {
+            Some((_, count)) => *count += 1,
+            None => required.push((letter, 1)),
+        }
+    }
+
+    for (letter, count) in required {
+        if guess.iter().filter(|&&ch| ch == letter).count() < count {
+            return Err(HardModeViolation::MustContain { letter, count });
+        }
+    }
+
+    Ok(())
+}
+
+/// A hint revealed by earlier feedback which a guess failed to use under hard mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum HardModeViolation {
+    /// The letter was previously found to be `Correct` in this (zero-indexed) position.
+    #[error("{} letter must be {}", ordinal(position + 1), letter.to_uppercase())]
+    MustBeAt { position: usize, letter: char },
+    /// The letter was previously found to appear at least `count` times.
+    #[error("guess must contain {}{}", letter.to_uppercase(), times(*count))]
+    MustContain { letter: char, count: usize },
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
+
+fn times(count: usize) -> String {
+    if count > 1 {
+        format!(" at least {} times", count)
+    } else {
+        String::new()
+    }
+}
+
+/// The rules which an oracle enforces, however it judges guesses.
+///
+/// This counts guesses against the limit, checks hard mode against the feedback given so far, and
+/// checks guesses against the allowed words.
+#[derive(Debug, Default)]
+pub struct Rules {
+    pub max_guesses: Option<usize>,
+    /// When set, each guess must use every hint revealed so far.
+    ///
+    /// Guesses which don't are rejected without counting against `max_guesses`.
+    pub hard_mode: bool,
+    /// When set, only these words (and the possible targets) are accepted as guesses.
+    ///
+    /// Other guesses are rejected without counting against `max_guesses`.
+    pub valid_guesses: Option<HashSet<String>>,
+    guesses: Cell<usize>,
+    /// every incorrect guess so far, with its feedback
+    history: RefCell<Vec<(String, Feedback)>>,
+}
+
+impl Rules {
+    /// Rules according to the game settings, loading the allowed words if guesses are validated.
+    #[cfg(feature = "wordlist")]
+    pub fn from_config(config: &crate::Config) -> Result<Self, Error> {
+        let valid_guesses = if config.validate_guesses {
+            let words = config
+                .words
+                .load_length_with(config.word_length, &config.normalizer)
+                .map_err(|err| Error::Io(Box::new(err)))?;
+            Some(words.into_iter().collect())
+        } else {
+            None
+        };
+        Ok(Rules {
+            max_guesses: config.max_guesses,
+            hard_mode: config.hard_mode,
+            valid_guesses,
+            ..Rules::default()
+        })
+    }
+
+    /// Check that a guess may be judged, and count it.
+    ///
+    /// `possible_target` should be set if the guess might be the target, in which case it is
+    /// accepted even if it is not among `valid_guesses`.
+    pub fn admit(&self, guess: &str, possible_target: bool) -> Result<(), Error> {
+        if let Some(valid_guesses) = &self.valid_guesses {
+            if !possible_target && !valid_guesses.contains(guess) {
+                return Err(Error::NotAWord(guess.to_owned()));
+            }
+        }
+        if self.hard_mode {
+            for (previous_guess, previous_feedback) in self.history.borrow().iter() {
+                check_hard_mode(guess, previous_guess, previous_feedback)?;
+            }
+        }
+
+        let guesses = self.guesses.get() + 1;
+        self.guesses.set(guesses);
+        if self
+            .max_guesses
+            .map(|max_guesses| guesses > max_guesses)
+            .unwrap_or_default()
+        {
+            return Err(Error::TooManyGuesses);
+        }
+        Ok(())
+    }
+
+    /// Stop counting the guess most recently admitted, because it was rejected after all.
+    ///
+    /// This suits an oracle which only learns that a guess is not a word after admitting it.
+    pub fn withdraw(&self) {
+        self.guesses.set(self.guesses.get().saturating_sub(1));
+    }
+
+    /// Remember the feedback given on an incorrect guess, for hard mode.
+    pub fn record(&self, guess: &str, feedback: &Feedback) {
+        self.history
+            .borrow_mut()
+            .push((guess.to_owned(), feedback.clone()));
+    }
+
+    /// The number of guesses admitted so far.
+    pub fn guesses(&self) -> usize {
+        self.guesses.get()
+    }
+
+    /// How many more guesses may be admitted, if they are limited.
+    pub fn guesses_remaining(&self) -> Option<usize> {
+        self.max_guesses
+            .map(|max_guesses| max_guesses.saturating_sub(self.guesses.get()))
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("the oracle will answer no more questions")]
     TooManyGuesses,
+    #[error("guess breaks hard mode: {0}")]
+    HardMode(#[from] HardModeViolation),
+    #[error("not in word list: {0}")]
+    NotAWord(String),
+    #[error("no words were available to choose a target from")]
+    NoWords,
     #[error("failed to communicate with the oracle")]
-    Io(#[source] Box<dyn std::error::Error>),
+    Io(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    /// Whether the oracle refused to judge this guess at all.
+    ///
+    /// Rejected guesses do not count as turns; the petitioner may try again with another word.
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Error::HardMode(_) | Error::NotAWord(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::HashMap;
+
+    /// A deliberately naive scorer, counting each target letter up front.
+    fn reference(guess: &str, target: &str) -> Feedback {
+        let guess: Vec<char> = guess.chars().collect();
+        let target: Vec<char> = target.chars().collect();
+
+        let mut remaining: HashMap<char, usize> = HashMap::new();
+        for ch in target.iter() {
+            *remaining.entry(*ch).or_default() += 1;
+        }
+        let mut fb = Vec::new();
+        for (idx, ch) in guess.iter().enumerate() {
+            if target.get(idx) == Some(ch) {
+                *remaining.get_mut(ch).unwrap() -= 1;
+            }
+        }
+        for (idx, ch) in guess.iter().enumerate() {
+            fb.push(if idx >= target.len() {
+                Disposition::Extra
+            } else if target[idx] == *ch {
+                Disposition::Correct
+            } else if remaining.get(ch).copied().unwrap_or_default() > 0 {
+                *remaining.get_mut(ch).unwrap() -= 1;
+                Disposition::WrongPosition
+            } else {
+                Disposition::NotInWord
+            });
+        }
+        for _ in guess.len()..target.len() {
+            fb.push(Disposition::Missing);
+        }
+        Feedback(fb)
+    }
+
+    fn random_word(rng: &mut StdRng, alphabet: &[u8], len: usize) -> String {
+        (0..len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_letters() {
+        let cases = [
+            ("speed", "abide", "BBYBY"),
+            ("abide", "speed", "BBBYY"),
+            ("geese", "speed", "BYGYB"),
+            ("geese", "geese", "GGGGG"),
+            ("llama", "hello", "YYBBB"),
+            ("lolly", "llama", "GBYBB"),
+        ];
+        for (guess, target, expected) in cases {
+            assert_eq!(
+                score(guess, target).to_string(),
+                expected,
+                "{guess}/{target}"
+            );
+            assert_eq!(score(guess, target), reference(guess, target));
+        }
+    }
+
+    #[test]
+    fn different_lengths() {
+        assert_eq!(score("ab", "xyzab").to_string(), "YY___");
+        assert_eq!(score("xyzab", "ab").to_string(), "BB+++");
+        assert_eq!(score("", "abc").to_string(), "___");
+        for (guess, target) in [("ab", "xyzab"), ("xyzab", "ab"), ("aab", "ba"), ("", "abc")] {
+            assert_eq!(
+                score(guess, target),
+                reference(guess, target),
+                "{guess}/{target}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_reference() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // a small alphabet makes repeated letters common
+        for alphabet in [&b"abc"[..], b"abcdefghijklmnopqrstuvwxyz"] {
+            for _ in 0..5000 {
+                let guess_len = rng.gen_range(0..8);
+                let target_len = if rng.gen_bool(0.8) {
+                    guess_len
+                } else {
+                    rng.gen_range(0..8)
+                };
+                let guess = random_word(&mut rng, alphabet, guess_len);
+                let target = random_word(&mut rng, alphabet, target_len);
+                assert_eq!(
+                    score(&guess, &target),
+                    reference(&guess, &target),
+                    "{guess}/{target}"
+                );
+            }
+        }
+    }
+
+    fn hard_mode(guess: &str, previous_guess: &str, previous_feedback: &str) -> Result<(), String> {
+        let feedback: Feedback = previous_feedback.parse().expect("valid feedback");
+        check_hard_mode(guess, previous_guess, &feedback).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn hard_mode_requires_hints() {
+        assert_eq!(hard_mode("glare", "stare", "BBGGG"), Ok(()));
+        assert_eq!(hard_mode("abbey", "eerie", "YBBBB"), Ok(()));
+        assert_eq!(hard_mode("sleep", "eerie", "YYBBB"), Ok(()));
+        assert_eq!(
+            hard_mode("crane", "hoard", "BBBGB"),
+            Err("4th letter must be R".to_owned())
+        );
+        assert_eq!(
+            hard_mode("abbey", "eerie", "YYBBB"),
+            Err("guess must contain E at least 2 times".to_owned())
+        );
+        assert_eq!(
+            hard_mode("mount", "cigar", "BBBYB"),
+            Err("guess must contain A".to_owned())
+        );
+        assert_eq!(
+            hard_mode("abcdefghijk", "xxxxxxxxxxz", "BBBBBBBBBBG"),
+            Err("11th letter must be Z".to_owned())
+        );
+        // a grey letter may still be reused, as only the hints are required
+        assert_eq!(hard_mode("speed", "geese", "BYGYB"), Ok(()));
+    }
+
+    #[test]
+    fn hard_mode_rejection_message() {
+        let feedback: Feedback = "GBBBB".parse().expect("valid feedback");
+        let err =
+            Error::from(check_hard_mode("trace", "cigar", &feedback).expect_err("C was correct"));
+        assert!(err.is_rejection());
+        assert_eq!(
+            err.to_string(),
+            "guess breaks hard mode: 1st letter must be C"
+        );
+    }
+
+    #[test]
+    fn ordinals() {
+        let cases = [
+            (1, "1st"),
+            (2, "2nd"),
+            (3, "3rd"),
+            (4, "4th"),
+            (11, "11th"),
+            (12, "12th"),
+            (13, "13th"),
+            (21, "21st"),
+            (22, "22nd"),
+            (111, "111th"),
+            (123, "123rd"),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(ordinal(n), expected);
+        }
+    }
 }
@@ -1,37 +1,132 @@
 //! This module uses a dictionary-based approach to solve wordle problems.
 
+use std::{collections::HashMap, sync::Arc};
+
 use itertools::Itertools;
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use super::strategy::{Random, Strategy};
+use crate::{
+    oracle::{check_hard_mode, Feedback},
+    wordlist::Source,
+};
+
+/// Bounds on the number of times a letter appears in the target word.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct LetterCount {
+    /// the letter appears at least this many times
+    min: usize,
+    /// the letter appears at most this many times, if known
+    max: Option<usize>,
+}
+
+impl LetterCount {
+    fn admits(&self, count: usize) -> bool {
+        count >= self.min && self.max.map(|max| count <= max).unwrap_or(true)
+    }
+}
 
 pub struct DictSolver {
+    /// how the next guess is chosen
+    pub strategy: Box<dyn Strategy>,
+    /// the source of any randomness the strategy needs
+    ///
+    /// Replace this with a seeded RNG for reproducible guesses.
+    pub rng: Box<dyn RngCore>,
+    /// Guess only words which use every hint revealed so far.
+    ///
+    /// This is set from the game settings, and also as soon as the oracle rejects a guess for
+    /// breaking hard mode.
+    pub hard_mode: bool,
+    /// every word of the right length, whether or not it is still a candidate
+    ///
+    /// This is shared, so that many solvers can be created from one loaded list.
+    dictionary: Arc<[String]>,
     /// the words which we can choose from
-    words: Vec<String>,
+    ///
+    /// This shares the dictionary until the first observation narrows it down.
+    words: Arc<[String]>,
     /// the previous guess
     last_guess: Option<String>,
     /// the set of characters known to be in the right place
     known_chars: Vec<Option<char>>,
     /// chars known to be in the word but not in this position
     known_wrong_chars: Vec<Vec<char>>,
-    /// the set of characters known to be in the word in unknown position
-    unknown_position: Vec<char>,
-    /// the set of characters known not to be in the word
-    not_in_word: Vec<char>,
+    /// known bounds on the number of times each character appears in the word
+    letter_counts: HashMap<char, LetterCount>,
+    /// every guess observed so far, with its feedback
+    history: Vec<(String, Feedback)>,
 }
 
-impl crate::Petitioner for DictSolver {
-    fn new(word_length: usize) -> Result<Box<Self>, super::Error> {
-        let words = crate::wordlist::load()
-            .map_err(|err| super::Error::Io(Box::new(err)))?
-            .filter(|word| word.chars().count() == word_length)
-            .collect();
-        Ok(Box::new(Self {
-            words,
+impl DictSolver {
+    /// Create a solver which guesses words of the given length from `words`.
+    pub fn from_source(word_length: usize, words: &Source) -> Result<Box<Self>, super::Error> {
+        let words = words
+            .load_length(word_length)
+            .map_err(|err| super::Error::Io(Box::new(err)))?;
+        Ok(Self::with_words(word_length, words))
+    }
+
+    /// Create a solver which guesses from these words, each of which has `word_length` characters.
+    ///
+    /// Pass an `Arc<[String]>` to share one list between many solvers without copying it; only the
+    /// words which survive each observation are copied.
+    pub fn with_words(word_length: usize, words: impl Into<Arc<[String]>>) -> Box<Self> {
+        let dictionary: Arc<[String]> = words.into();
+        debug_assert!(dictionary
+            .iter()
+            .all(|word| word.chars().count() == word_length));
+        Box::new(Self {
+            strategy: Box::new(Random),
+            rng: Box::new(StdRng::from_entropy()),
+            hard_mode: false,
+            words: Arc::clone(&dictionary),
+            dictionary,
             last_guess: None,
             known_chars: vec![None; word_length],
             known_wrong_chars: vec![Vec::new(); word_length],
-            unknown_position: Vec::with_capacity(5),
-            not_in_word: Vec::with_capacity(26 - 5),
-        }))
+            letter_counts: HashMap::new(),
+            history: Vec::new(),
+        })
+    }
+
+    /// Make this solver's guesses reproducible, by seeding its RNG.
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = Box::new(StdRng::seed_from_u64(seed));
+    }
+
+    /// The words which fit every observation so far.
+    pub fn candidates(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Drop every word from the dictionary which doesn't use all the hints revealed so far.
+    fn restrict_to_hard_mode(&mut self) {
+        let history = &self.history;
+        retain(&mut self.dictionary, |word| {
+            history
+                .iter()
+                .all(|(guess, feedback)| check_hard_mode(word, guess, feedback).is_ok())
+        });
+    }
+
+    /// The word the strategy would guess next, without committing to guess it.
+    pub fn suggest(&mut self) -> Option<String> {
+        self.strategy
+            .choose(&self.words, &self.dictionary, &mut *self.rng)
+    }
+}
+
+impl crate::Petitioner for DictSolver {
+    fn new(config: &crate::Config) -> Result<Box<Self>, super::Error> {
+        let words = config
+            .words
+            .load_length_with(config.word_length, &config.normalizer)
+            .map_err(|err| super::Error::Io(Box::new(err)))?;
+        let mut solver = Self::with_words(config.word_length, words);
+        solver.rng = Box::new(config.petitioner_rng());
+        solver.hard_mode = config.hard_mode;
+        Ok(solver)
     }
 
     fn prepare_guess(&mut self) -> Result<String, super::Error> {
@@ -39,51 +134,68 @@ impl crate::Petitioner for DictSolver {
             return Err(super::Error::AwaitingFeedback);
         }
 
-        let mut rng = rand::thread_rng();
-        self.last_guess = self.words.choose(&mut rng).cloned();
+        self.last_guess = self.suggest();
         self.last_guess.clone().ok_or(super::Error::Stumped)
     }
 
     fn feedback(&mut self, feedback: crate::oracle::Feedback) -> Result<(), super::Error> {
-        use crate::oracle::Disposition::*;
-
         let last_guess = match self.last_guess.take() {
             Some(last_guess) => last_guess,
             None => return Err(super::Error::UnexpectedFeedback),
         };
+        self.observe(&last_guess, feedback)
+    }
 
-        for (idx, eob) in last_guess.chars().zip_longest(feedback).enumerate() {
-            match eob {
-                itertools::EitherOrBoth::Both(ch, disp) => match disp {
-                    NotInWord => {
-                        self.not_in_word.push(ch);
-                    }
-                    WrongPosition => {
-                        self.unknown_position.push(ch);
-                        self.known_wrong_chars[idx].push(ch);
-                    }
-                    Correct => {
-                        self.known_chars[idx] = Some(ch);
-                        self.unknown_position.retain(|unk| unk != &ch);
-                    }
-                    Missing | Extra => {
-                        unreachable!("this petitioner always submits words of the right length")
-                    }
-                },
-                itertools::EitherOrBoth::Left(_) => {
-                    return Err(super::Error::InappropriateFeedback)
+    fn observe(
+        &mut self,
+        guess: &str,
+        feedback: crate::oracle::Feedback,
+    ) -> Result<(), super::Error> {
+        use crate::oracle::Disposition::*;
+
+        self.last_guess = None;
+        let word_length = self.known_chars.len();
+        if guess.chars().count() != word_length
+            || feedback.len() != word_length
+            || feedback
+                .iter()
+                .any(|&disp| disp == Missing || disp == Extra)
+        {
+            return Err(super::Error::InappropriateFeedback);
+        }
+
+        // per character in this guess: how many times it was confirmed present, and whether any
+        // further copy was rejected
+        let mut tally: HashMap<char, (usize, bool)> = HashMap::new();
+
+        for (idx, (ch, disp)) in guess.chars().zip(feedback.iter().copied()).enumerate() {
+            match disp {
+                NotInWord => {
+                    self.known_wrong_chars[idx].push(ch);
+                    tally.entry(ch).or_default().1 = true;
+                }
+                WrongPosition => {
+                    self.known_wrong_chars[idx].push(ch);
+                    tally.entry(ch).or_default().0 += 1;
                 }
-                itertools::EitherOrBoth::Right(_) => {
-                    unreachable!("this petitioner always submits words of the correct length")
+                Correct => {
+                    self.known_chars[idx] = Some(ch);
+                    tally.entry(ch).or_default().0 += 1;
                 }
+                Missing | Extra => unreachable!("feedback was checked above"),
             }
         }
 
-        self.words.retain(|word| {
-            // eliminate words with characters known to be wrong
-            if word.chars().any(|ch| self.not_in_word.contains(&ch)) {
-                return false;
+        for (ch, (present, rejected)) in tally {
+            let count = self.letter_counts.entry(ch).or_default();
+            count.min = count.min.max(present);
+            if rejected {
+                // a grey copy means every copy of this letter in the word has been found
+                count.max = Some(present);
             }
+        }
+
+        retain(&mut self.words, |word| {
             // choose only words with characters matching known-good characters
             if word
                 .chars()
@@ -100,17 +212,151 @@ impl crate::Petitioner for DictSolver {
             {
                 return false;
             }
-            // choose only words which contain all necessary characters
-            for need_ch in self.unknown_position.iter() {
-                if !word.chars().contains(need_ch) {
-                    return false;
-                }
+            // choose only words which contain each character an acceptable number of times
+            let counts = word.chars().counts();
+            if self
+                .letter_counts
+                .iter()
+                .any(|(ch, bounds)| !bounds.admits(counts.get(ch).copied().unwrap_or_default()))
+            {
+                return false;
             }
 
             // other words must be ok
             true
         });
 
+        self.history.push((guess.to_owned(), feedback));
+        if self.hard_mode {
+            self.restrict_to_hard_mode();
+        }
+
         Ok(())
     }
+
+    fn rejected(&mut self, _guess: &str, reason: crate::oracle::Error) -> Result<(), super::Error> {
+        let last_guess = match self.last_guess.take() {
+            Some(last_guess) => last_guess,
+            None => return Err(super::Error::UnexpectedFeedback),
+        };
+
+        if let crate::oracle::Error::HardMode(_) = reason {
+            // Rather than discover the rule one word at a time, keep only the words which obey it.
+            self.hard_mode = true;
+            self.restrict_to_hard_mode();
+        }
+        // Neither an unknown word nor one which breaks hard mode will ever be accepted later.
+        if self.dictionary.contains(&last_guess) {
+            retain(&mut self.dictionary, |word| word != &last_guess);
+        }
+        if self.words.contains(&last_guess) {
+            retain(&mut self.words, |word| word != &last_guess);
+        }
+
+        Ok(())
+    }
+}
+
+/// Keep only the words for which `keep` holds, copying them out of a shared list.
+fn retain<Keep>(words: &mut Arc<[String]>, mut keep: Keep)
+where
+    Keep: FnMut(&String) -> bool,
+{
+    *words = words.iter().filter(|word| keep(word)).cloned().collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{oracle::score, Petitioner};
+    use rand::Rng;
+
+    fn solver(words: &[String]) -> Box<DictSolver> {
+        DictSolver::with_words(words[0].chars().count(), words.to_vec())
+    }
+
+    /// Observe each guess scored against `target`, checking that the target is never ruled out.
+    fn assert_keeps_target(solver: &mut DictSolver, guesses: &[String], target: &str) {
+        for guess in guesses {
+            solver
+                .observe(guess, score(guess, target))
+                .expect("feedback fits the guess");
+            assert!(
+                solver.candidates().iter().any(|word| word == target),
+                "{target} was ruled out by {guesses:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_target_with_repeated_letters() {
+        let words: Vec<String> = [
+            "geese", "llama", "eerie", "allay", "speed", "lolly", "sheep",
+        ]
+        .iter()
+        .map(|&word| word.to_owned())
+        .collect();
+        for target in words.iter() {
+            for first in words.iter() {
+                for second in words.iter() {
+                    let mut solver = solver(&words);
+                    assert_keeps_target(&mut solver, &[first.clone(), second.clone()], target);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_target_with_random_words() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // a small alphabet makes repeated letters common
+        let alphabet = b"abc";
+        for _ in 0..500 {
+            let words: Vec<String> = (0..30)
+                .map(|_| {
+                    (0..5)
+                        .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+                        .collect()
+                })
+                .collect();
+            let target = &words[rng.gen_range(0..words.len())];
+            let guesses: Vec<String> = (0..4)
+                .map(|_| words[rng.gen_range(0..words.len())].clone())
+                .collect();
+            assert_keeps_target(&mut solver(&words), &guesses, target);
+        }
+    }
+
+    /// Play against `target`, returning every guess made.
+    fn play(solver: &mut DictSolver, target: &str) -> Vec<String> {
+        let mut guesses = Vec::new();
+        loop {
+            let guess = solver.prepare_guess().expect("the target is a candidate");
+            guesses.push(guess.clone());
+            let feedback = score(&guess, target);
+            if feedback.is_correct() {
+                return guesses;
+            }
+            solver.feedback(feedback).expect("feedback fits the guess");
+        }
+    }
+
+    #[test]
+    fn seeded_guesses_are_reproducible() {
+        let words: Vec<String> = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf", "model", "karma", "stink", "grade", "quiet", "bench",
+        ]
+        .iter()
+        .map(|&word| word.to_owned())
+        .collect();
+        let seeded = |seed| {
+            let mut solver = solver(&words);
+            solver.seed(seed);
+            play(&mut solver, "stink")
+        };
+        assert_eq!(seeded(7), ["evade", "sissy", "stink"]);
+        assert_eq!(seeded(7), seeded(7));
+        assert_eq!(seeded(8), ["dwarf", "blush", "stink"]);
+    }
 }
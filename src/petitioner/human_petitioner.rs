@@ -1,24 +1,50 @@
-use std::io::Write;
+use std::io::{self, BufRead, Write};
 
-use crate::petitioner::Petitioner;
+use crate::petitioner::{Error, Petitioner};
 
 #[derive(Debug)]
 pub struct HumanPetitioner;
 
 impl Petitioner for HumanPetitioner {
-    fn new(word_length: usize) -> Result<Box<Self>, super::Error> {
-        println!("You must guess a word of {} characters.", word_length);
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error> {
+        println!(
+            "You must guess a word of {} characters.",
+            config.word_length
+        );
         Ok(Box::new(Self))
     }
 
-    fn prepare_guess(&mut self) -> Result<String, super::Error> {
-        let mut stdout = ezio::stdio::stdout();
-        stdout.write_all(b"> ").expect("can write to stdout");
-        stdout.flush().expect("can write to stdout");
-        Ok(ezio::stdio::read_line())
+    fn prepare_guess(&mut self) -> Result<String, Error> {
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(b"> ")
+            .map_err(|err| Error::Io(Box::new(err)))?;
+        stdout.flush().map_err(|err| Error::Io(Box::new(err)))?;
+
+        let mut line = String::new();
+        let read = io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|err| Error::Io(Box::new(err)))?;
+        if read == 0 {
+            return Err(Error::Io(Box::new(io::Error::from(
+                io::ErrorKind::UnexpectedEof,
+            ))));
+        }
+        Ok(line.trim().to_owned())
+    }
+
+    fn feedback(&mut self, _feedback: crate::oracle::Feedback) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn observe(&mut self, guess: &str, feedback: crate::oracle::Feedback) -> Result<(), Error> {
+        crate::print_feedback(guess, &feedback);
+        Ok(())
     }
 
-    fn feedback(&mut self, _feedback: crate::oracle::Feedback) -> Result<(), super::Error> {
+    fn rejected(&mut self, _guess: &str, reason: crate::oracle::Error) -> Result<(), Error> {
+        println!("{}; try again", reason);
         Ok(())
     }
 }
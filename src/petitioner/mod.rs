@@ -2,6 +2,8 @@
 pub mod dict_solver;
 #[cfg(feature = "human_petitioner")]
 pub mod human_petitioner;
+#[cfg(feature = "dict_solver")]
+pub mod strategy;
 
 /// A petitioner must consider the pronouncements of the Oracle to discover a secret word.
 ///
@@ -10,10 +12,16 @@ pub mod human_petitioner;
 /// - `new`
 /// - until a correct guess or oracle guess limit reached:
 ///     - `prepare_guess`
-///     - `feedback`
+///     - `feedback`, or `rejected` if the oracle refused to judge the guess
+///
+/// Between turns, `observe` may also be called with guesses made by someone else.
 pub trait Petitioner {
-    /// Create a petitioner who will guess words of this number of characters.
-    fn new(word_length: usize) -> Result<Box<Self>, Error>;
+    /// Create a petitioner according to the game settings.
+    ///
+    /// It will guess words of `config.word_length` characters.
+    fn new(config: &crate::Config) -> Result<Box<Self>, Error>
+    where
+        Self: Sized;
 
     /// The petitioner must create a guess satisfying known constraints.
     ///
@@ -24,12 +32,31 @@ pub trait Petitioner {
     ///
     /// If called out of sequence, it should return `Error::UnexpectedFeedback`.
     fn feedback(&mut self, feedback: crate::oracle::Feedback) -> Result<(), Error>;
+
+    /// Send feedback about a guess which this petitioner did not necessarily prepare.
+    ///
+    /// This lets a petitioner follow a game played by someone else: for example to assist a
+    /// player, to resume from a transcript, or to take over a game part way through. Any guess
+    /// prepared but not yet judged is abandoned. By default, observations are not supported.
+    fn observe(&mut self, guess: &str, feedback: crate::oracle::Feedback) -> Result<(), Error> {
+        let _ = (guess, feedback);
+        Err(Error::CannotObserve)
+    }
+
+    /// Inform the petitioner that the oracle refused to judge the previous guess.
+    ///
+    /// The rejection did not use up a turn; the petitioner should prepare a different guess. By
+    /// default, this is fatal.
+    fn rejected(&mut self, guess: &str, reason: crate::oracle::Error) -> Result<(), Error> {
+        let _ = guess;
+        Err(Error::Rejected(reason))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("failed to communicate with petitioner")]
-    Io(#[source] Box<dyn std::error::Error>),
+    Io(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("could not determine a word fitting all constraints")]
     Stumped,
     #[error("cannot prepare a new guess while awaiting feedback on a previous guess")]
@@ -38,4 +65,8 @@ pub enum Error {
     UnexpectedFeedback,
     #[error("feedback provided is inappropriate for the provided guess")]
     InappropriateFeedback,
+    #[error("this petitioner cannot follow guesses it did not prepare")]
+    CannotObserve,
+    #[error("the oracle rejected the guess")]
+    Rejected(#[source] crate::oracle::Error),
 }
@@ -0,0 +1,282 @@
+//! Strategies choose which word a dictionary-based solver should guess next.
+//!
+//! Each solver is responsible for narrowing down the words which fit all known constraints; the
+//! strategy only chooses among them, or among the wider dictionary of guessable words.
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, RngCore};
+
+/// A strategy chooses the next guess for a dictionary-based solver.
+pub trait Strategy {
+    /// Choose the next guess.
+    ///
+    /// `candidates` are the words which fit every known constraint; `dictionary` is every word
+    /// which may be guessed. `None` indicates that no guess could be chosen.
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        dictionary: &[String],
+        rng: &mut dyn RngCore,
+    ) -> Option<String>;
+}
+
+/// The names of the built-in strategies, as accepted by [`by_name`].
+pub const NAMES: &[&str] = &["random", "frequency", "entropy", "minimax", "expected-size"];
+
+/// Construct a built-in strategy by name.
+pub fn by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    let strategy: Box<dyn Strategy> = match name {
+        "random" => Box::new(Random),
+        "frequency" => Box::new(MostFrequentLetters),
+        "entropy" => Box::new(Entropy),
+        "minimax" => Box::new(Minimax),
+        "expected-size" => Box::new(ExpectedRemaining),
+        _ => return None,
+    };
+    Some(strategy)
+}
+
+/// Construct a built-in strategy by name, or describe the names expected.
+pub fn parse(name: &str) -> Result<Box<dyn Strategy>, String> {
+    by_name(name).ok_or_else(|| {
+        format!(
+            "unknown strategy {:?}; expected one of {}",
+            name,
+            NAMES.join(", ")
+        )
+    })
+}
+
+/// Guess a candidate chosen uniformly at random.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Random;
+
+impl Strategy for Random {
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        _dictionary: &[String],
+        rng: &mut dyn RngCore,
+    ) -> Option<String> {
+        candidates.choose(rng).cloned()
+    }
+}
+
+/// Guess the candidate whose distinct letters are most common among all candidates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MostFrequentLetters;
+
+impl Strategy for MostFrequentLetters {
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        _dictionary: &[String],
+        _rng: &mut dyn RngCore,
+    ) -> Option<String> {
+        // how many candidates contain each letter
+        let frequencies = candidates
+            .iter()
+            .flat_map(|word| word.chars().unique())
+            .counts();
+        candidates
+            .iter()
+            .max_by_key(|word| {
+                word.chars()
+                    .unique()
+                    .map(|ch| frequencies[&ch])
+                    .sum::<usize>()
+            })
+            .cloned()
+    }
+}
+
+/// Guess the word whose feedback is expected to reveal the most information about the candidates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Entropy;
+
+impl Strategy for Entropy {
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        dictionary: &[String],
+        _rng: &mut dyn RngCore,
+    ) -> Option<String> {
+        opening("entropy", candidates, dictionary, || {
+            best_partition(candidates, dictionary, |buckets, total| {
+                let entropy: f64 = buckets
+                    .map(|count| {
+                        let p = count as f64 / total as f64;
+                        -p * p.log2()
+                    })
+                    .sum();
+                -entropy
+            })
+        })
+    }
+}
+
+/// Guess the word which minimizes the number of candidates remaining in the worst case.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        dictionary: &[String],
+        _rng: &mut dyn RngCore,
+    ) -> Option<String> {
+        opening("minimax", candidates, dictionary, || {
+            best_partition(candidates, dictionary, |buckets, _| {
+                buckets.max().unwrap_or_default() as f64
+            })
+        })
+    }
+}
+
+/// Guess the word which minimizes the expected number of candidates remaining afterwards.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExpectedRemaining;
+
+impl Strategy for ExpectedRemaining {
+    fn choose(
+        &mut self,
+        candidates: &[String],
+        dictionary: &[String],
+        _rng: &mut dyn RngCore,
+    ) -> Option<String> {
+        opening("expected-size", candidates, dictionary, || {
+            best_partition(candidates, dictionary, |buckets, total| {
+                // each bucket is reached with probability `count / total`, and leaves `count` words
+                buckets.map(|count| (count * count) as f64).sum::<f64>() / total as f64
+            })
+        })
+    }
+}
+
+/// Opening guesses already chosen in this process, by strategy name and dictionary fingerprint.
+static OPENINGS: LazyLock<Mutex<HashMap<(&'static str, u64), String>>> =
+    LazyLock::new(Default::default);
+
+/// Choose the opening guess once per strategy and dictionary, and remember it.
+///
+/// Before any feedback the candidates are the whole dictionary, so a deterministic strategy makes
+/// the same costly choice at the start of every game. When that is the case, the choice is made
+/// only once in each process, and later games reuse it.
+fn opening<Choose>(
+    strategy: &'static str,
+    candidates: &[String],
+    dictionary: &[String],
+    choose: Choose,
+) -> Option<String>
+where
+    Choose: FnOnce() -> Option<String>,
+{
+    if candidates != dictionary {
+        return choose();
+    }
+
+    let key = (strategy, fingerprint(dictionary));
+    // hold the lock while choosing, so that parallel games make the choice only once
+    let mut openings = OPENINGS.lock().expect("opening cache is never poisoned");
+    if let Some(guess) = openings.get(&key) {
+        return Some(guess.clone());
+    }
+    let guess = choose()?;
+    openings.insert(key, guess.clone());
+    Some(guess)
+}
+
+/// Identify a dictionary by its contents.
+fn fingerprint(dictionary: &[String]) -> u64 {
+    // 64-bit FNV-1a, with a zero byte after each word
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    dictionary
+        .iter()
+        .flat_map(|word| word.bytes().chain(std::iter::once(0)))
+        .fold(OFFSET, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+/// Compactly identify the feedback which `guess` would receive against `target`.
+///
+/// This is equivalent to `oracle::score`, but doesn't allocate. Both words must have the same
+/// length, which may not exceed 64 characters.
+fn pattern(guess: &[char], target: &[char]) -> u128 {
+    debug_assert_eq!(guess.len(), target.len());
+    debug_assert!(target.len() <= 64);
+
+    // bit `n` is set when target letter `n` has been matched by some guess letter
+    let mut matched = 0_u64;
+    for (idx, (have, want)) in guess.iter().zip(target).enumerate() {
+        if have == want {
+            matched |= 1 << idx;
+        }
+    }
+
+    let mut code = 0;
+    for (idx, have) in guess.iter().enumerate().rev() {
+        code *= 3;
+        if target[idx] == *have {
+            code += 2;
+        } else if let Some(pos) =
+            (0..target.len()).find(|&pos| matched & (1 << pos) == 0 && target[pos] == *have)
+        {
+            matched |= 1 << pos;
+            code += 1;
+        }
+    }
+    code
+}
+
+/// Choose the word from `dictionary` which partitions the `candidates` best.
+///
+/// Each guess splits the candidates into buckets which would each receive identical feedback.
+/// `cost` is given the size of each bucket and the total number of candidates; the guess with the
+/// lowest cost wins. Ties are broken in favor of words which are themselves candidates, as they
+/// might win outright.
+fn best_partition<Cost>(candidates: &[String], dictionary: &[String], cost: Cost) -> Option<String>
+where
+    Cost: Fn(&mut dyn Iterator<Item = usize>, usize) -> f64,
+{
+    // with so few candidates, no guess could do better than guessing one of them
+    if candidates.len() <= 2 {
+        return candidates.first().cloned();
+    }
+
+    let candidate_chars: Vec<Vec<char>> = candidates
+        .iter()
+        .map(|word| word.chars().collect())
+        .collect();
+    let mut buckets: HashMap<u128, usize> = HashMap::new();
+
+    let mut best: Option<(f64, bool, &String)> = None;
+    for guess in dictionary {
+        let guess_chars: Vec<char> = guess.chars().collect();
+        buckets.clear();
+        for target in candidate_chars.iter() {
+            *buckets.entry(pattern(&guess_chars, target)).or_default() += 1;
+        }
+        let cost = cost(&mut buckets.values().copied(), candidates.len());
+
+        let is_candidate = || candidates.contains(guess);
+        let better = match best {
+            None => true,
+            Some((best_cost, best_is_candidate, _)) => {
+                cost < best_cost || (cost == best_cost && !best_is_candidate && is_candidate())
+            }
+        };
+        if better {
+            best = Some((cost, is_candidate(), guess));
+        }
+    }
+
+    best.map(|(_, _, guess)| guess.clone())
+}
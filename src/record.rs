@@ -0,0 +1,103 @@
+//! A transcript of a finished game.
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{oracle, oracle::Feedback, petitioner};
+
+/// A judged guess, and the oracle's feedback on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Turn {
+    pub guess: String,
+    /// For the winning guess, every letter is `Correct`.
+    pub feedback: Feedback,
+}
+
+impl Turn {
+    /// The turn on which the petitioner guessed the target.
+    pub(crate) fn winning(guess: String) -> Self {
+        let feedback = Feedback::correct(guess.chars().count());
+        Turn { guess, feedback }
+    }
+}
+
+/// How a game ended.
+#[derive(Clone, Debug)]
+pub enum Outcome {
+    /// The petitioner guessed the target.
+    Won,
+    /// The petitioner used every guess the oracle allowed without finding the target.
+    Lost {
+        /// The target word, if the oracle revealed it.
+        target: Option<String>,
+    },
+    /// The game was abandoned because the oracle or petitioner failed.
+    Error(GameError),
+}
+
+/// The failure which abandoned a game.
+///
+/// The errors are shared, so that records can be cloned.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum GameError {
+    #[error(transparent)]
+    Oracle(Arc<oracle::Error>),
+    #[error(transparent)]
+    Petitioner(Arc<petitioner::Error>),
+}
+
+impl From<oracle::Error> for GameError {
+    fn from(err: oracle::Error) -> Self {
+        GameError::Oracle(Arc::new(err))
+    }
+}
+
+impl From<petitioner::Error> for GameError {
+    fn from(err: petitioner::Error) -> Self {
+        GameError::Petitioner(Arc::new(err))
+    }
+}
+
+/// Everything which happened in a game.
+#[derive(Clone, Debug)]
+pub struct GameRecord {
+    /// Every guess the oracle judged, in order. Rejected guesses are not included.
+    pub turns: Vec<Turn>,
+    pub outcome: Outcome,
+    /// How long the game took, from the first guess to the end.
+    pub duration: Duration,
+    /// The seed from which the players were constructed, if known; see [`crate::wordle`].
+    pub seed: Option<u64>,
+}
+
+impl GameRecord {
+    /// The number of guesses the oracle judged.
+    pub fn guesses(&self) -> usize {
+        self.turns.len()
+    }
+
+    /// Whether the petitioner guessed the target.
+    pub fn is_won(&self) -> bool {
+        matches!(self.outcome, Outcome::Won)
+    }
+
+    /// The target word, if it is known: either it was guessed, or the oracle revealed it.
+    pub fn target(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Won => self.turns.last().map(|turn| turn.guess.as_str()),
+            Outcome::Lost { target } => target.as_deref(),
+            Outcome::Error(_) => None,
+        }
+    }
+}
+
+/// Describe an error along with each of its sources, e.g. "failed to communicate: broken pipe".
+pub fn describe(err: &dyn std::error::Error) -> String {
+    let mut description = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        description.push_str(": ");
+        description.push_str(&err.to_string());
+        source = err.source();
+    }
+    description
+}
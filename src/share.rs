@@ -0,0 +1,55 @@
+//! Summarize a finished game as a grid of coloured squares, for posting without spoilers.
+
+use std::fmt::Write;
+
+use crate::{oracle::Disposition, GameRecord, Outcome};
+
+/// Which variant of the grid to produce.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShareOptions {
+    /// Shown in the header as the number of guesses allowed, e.g. "3/6".
+    pub max_guesses: Option<usize>,
+    /// Marks the header with `*`, as the game was played in hard mode.
+    pub hard_mode: bool,
+    /// Uses orange and blue in place of green and yellow.
+    pub high_contrast: bool,
+}
+
+/// The square for a single letter.
+///
+/// This is [`Disposition::emoji`], except that the high-contrast variant uses orange and blue.
+pub fn square(disposition: Disposition, high_contrast: bool) -> char {
+    match (disposition, high_contrast) {
+        (Disposition::WrongPosition, true) => '🟦',
+        (Disposition::Correct, true) => '🟧',
+        _ => disposition.emoji(),
+    }
+}
+
+/// Format a game as a header such as "Wordler 3/6", followed by one row of squares per guess.
+///
+/// A game which was not won is scored as "X".
+pub fn grid(record: &GameRecord, options: &ShareOptions) -> String {
+    let mut out = String::from("Wordler ");
+    match record.outcome {
+        Outcome::Won => write!(out, "{}", record.guesses()),
+        _ => write!(out, "X"),
+    }
+    .expect("writing to a string succeeds");
+    if let Some(max_guesses) = options.max_guesses {
+        write!(out, "/{}", max_guesses).expect("writing to a string succeeds");
+    }
+    if options.hard_mode {
+        out.push('*');
+    }
+
+    for turn in record.turns.iter() {
+        out.push('\n');
+        out.extend(
+            turn.feedback
+                .iter()
+                .map(|&disposition| square(disposition, options.high_contrast)),
+        );
+    }
+    out
+}
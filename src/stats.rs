@@ -0,0 +1,165 @@
+//! A history of finished games, kept between sessions.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+};
+
+use crate::{GameRecord, Outcome};
+
+/// The guess distribution always has a row for at least this many guesses.
+const MIN_DISTRIBUTION_ROWS: usize = 6;
+
+/// The result of each game played so far, oldest first.
+///
+/// Stored one game per line: the number of guesses for a win, or `X` for a loss.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// the number of guesses taken to win each game, or `None` if it was lost
+    pub results: Vec<Option<usize>>,
+}
+
+impl Stats {
+    /// Load the stats from the data directory, or start afresh if none have been saved.
+    pub fn load() -> Result<Self, Error> {
+        let file = match File::open(stats_path()?) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Stats::default()),
+            Err(err) => return Err(Error::Read(err)),
+        };
+        let mut results = Vec::new();
+        for (idx, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(Error::Read)?;
+            let result = match line.trim() {
+                "" => continue,
+                "X" => None,
+                guesses => Some(guesses.parse().map_err(|_| Error::Parse(idx + 1))?),
+            };
+            results.push(result);
+        }
+        Ok(Stats { results })
+    }
+
+    /// Add a finished game, and append it to the file in the data directory.
+    ///
+    /// Games which ended in an error are not recorded.
+    pub fn record(&mut self, record: &GameRecord) -> Result<(), Error> {
+        let result = match record.outcome {
+            Outcome::Won => Some(record.guesses()),
+            Outcome::Lost { .. } => None,
+            Outcome::Error(_) => return Ok(()),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(stats_path()?)
+            .map_err(Error::Write)?;
+        match result {
+            Some(guesses) => writeln!(file, "{}", guesses),
+            None => writeln!(file, "X"),
+        }
+        .map_err(Error::Write)?;
+
+        self.results.push(result);
+        Ok(())
+    }
+
+    /// The number of games played.
+    pub fn played(&self) -> usize {
+        self.results.len()
+    }
+
+    /// The number of games won.
+    pub fn wins(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.is_some())
+            .count()
+    }
+
+    /// The percentage of games won, from 0 to 100.
+    pub fn win_percentage(&self) -> f64 {
+        100.0 * self.wins() as f64 / self.played().max(1) as f64
+    }
+
+    /// The number of games won since the last loss.
+    pub fn current_streak(&self) -> usize {
+        self.results
+            .iter()
+            .rev()
+            .take_while(|result| result.is_some())
+            .count()
+    }
+
+    /// The longest run of consecutive wins.
+    pub fn max_streak(&self) -> usize {
+        self.results
+            .split(|result| result.is_none())
+            .map(|streak| streak.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// How many games were won in each number of guesses.
+    pub fn distribution(&self) -> BTreeMap<usize, usize> {
+        let mut distribution = BTreeMap::new();
+        for guesses in self.results.iter().flatten() {
+            *distribution.entry(*guesses).or_default() += 1;
+        }
+        distribution
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BAR_WIDTH: usize = 30;
+
+        writeln!(f, "played:         {}", self.played())?;
+        writeln!(f, "win %:          {:.0}", self.win_percentage())?;
+        writeln!(f, "current streak: {}", self.current_streak())?;
+        writeln!(f, "max streak:     {}", self.max_streak())?;
+
+        writeln!(f, "guess distribution:")?;
+        let distribution = self.distribution();
+        let most = distribution.values().copied().max().unwrap_or_default();
+        let rows = distribution
+            .keys()
+            .copied()
+            .max()
+            .unwrap_or_default()
+            .max(MIN_DISTRIBUTION_ROWS);
+        for guesses in 1..=rows {
+            let games = distribution.get(&guesses).copied().unwrap_or_default();
+            let width = (games * BAR_WIDTH).div_ceil(most.max(1));
+            writeln!(f, "{:>3} | {} {}", guesses, "#".repeat(width), games)?;
+        }
+        Ok(())
+    }
+}
+
+/// The path of the stats file, creating its directory if necessary.
+fn stats_path() -> Result<PathBuf, Error> {
+    let dir = dirs::data_dir().ok_or(Error::NoDataDir)?.join("wordler");
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).map_err(Error::CreateDataDir)?;
+    }
+    Ok(dir.join("stats.txt"))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no data directory could be constructed")]
+    NoDataDir,
+    #[error("could not create the data directory")]
+    CreateDataDir(#[source] io::Error),
+    #[error("could not read the stats file")]
+    Read(#[source] io::Error),
+    #[error("could not write the stats file")]
+    Write(#[source] io::Error),
+    #[error("the stats file is corrupt at line {0}")]
+    Parse(usize),
+}
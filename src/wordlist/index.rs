@@ -0,0 +1,227 @@
+//! A binary index of a cached word list, grouped by word length.
+//!
+//! Most consumers only want words of a single length. The index lets them read just that bucket,
+//! instead of decompressing the whole list and filtering it.
+//!
+//! The format is little-endian throughout:
+//!
+//! - the magic bytes `WDLX` and a format version byte
+//! - the number of buckets, as a `u32`
+//! - for each bucket, its word length in characters (`u32`), its number of words (`u32`), and
+//!   the offset (`u64`) and size (`u64`) in bytes of its data, relative to the end of this table
+//! - the data of each bucket: its words, each followed by a newline
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::Path,
+};
+
+use super::{cache_path, load, Error, List};
+
+const MAGIC: &[u8; 4] = b"WDLX";
+const VERSION: u8 = 1;
+const HEADER_SIZE: u64 = 4 + 1 + 4;
+const ENTRY_SIZE: u64 = 4 + 4 + 8 + 8;
+
+/// One bucket's entry in the index table.
+struct Entry {
+    length: u32,
+    count: u32,
+    offset: u64,
+    size: u64,
+}
+
+/// Build the index for a list from its cache, replacing any existing index.
+///
+/// Returns the number of buckets written.
+pub fn build(list: List) -> Result<usize, Error> {
+    let path = cache_path(list)?.with_extension("idx");
+    write_path(&path, load(list)?)
+}
+
+/// Write an index of `words` to `path`, replacing any existing file.
+fn write_path(path: &Path, words: impl Iterator<Item = String>) -> Result<usize, Error> {
+    let mut buckets: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for word in words {
+        buckets.entry(word.chars().count()).or_default().push(word);
+    }
+
+    let mut table = Vec::with_capacity(buckets.len());
+    let mut offset = 0;
+    for (&length, words) in buckets.iter() {
+        let size = words.iter().map(|word| word.len() as u64 + 1).sum();
+        table.push(Entry {
+            length: length as u32,
+            count: words.len() as u32,
+            offset,
+            size,
+        });
+        offset += size;
+    }
+
+    // write to a temporary file first, so that readers never see a partial index
+    let tmp_path = path.with_extension("idx.tmp");
+    let file = File::create(&tmp_path).map_err(Error::WriteIndex)?;
+    let mut writer = BufWriter::new(file);
+    let write = |writer: &mut BufWriter<File>| -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(table.len() as u32).to_le_bytes())?;
+        for entry in table.iter() {
+            writer.write_all(&entry.length.to_le_bytes())?;
+            writer.write_all(&entry.count.to_le_bytes())?;
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.size.to_le_bytes())?;
+        }
+        for word in buckets.values().flatten() {
+            writer.write_all(word.as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    };
+    write(&mut writer).map_err(Error::WriteIndex)?;
+    drop(writer);
+    std::fs::rename(&tmp_path, path).map_err(Error::WriteIndex)?;
+
+    Ok(table.len())
+}
+
+/// Remove a list's index, if any, because its cache has changed.
+pub(super) fn invalidate(list: List) -> Result<(), Error> {
+    let path = cache_path(list)?.with_extension("idx");
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(Error::WriteIndex(err)),
+        _ => Ok(()),
+    }
+}
+
+/// Read the words of a single length from a list's index, without reading the rest of the list.
+fn read(list: List, length: usize) -> Result<Vec<String>, Error> {
+    read_path(&cache_path(list)?.with_extension("idx"), length)
+}
+
+/// Read the words of a single length from the index at `path`.
+fn read_path(path: &Path, length: usize) -> Result<Vec<String>, Error> {
+    let mut reader = BufReader::new(File::open(path).map_err(Error::ReadIndex)?);
+
+    let mut header = [0; HEADER_SIZE as usize];
+    reader.read_exact(&mut header).map_err(Error::ReadIndex)?;
+    if &header[..4] != MAGIC || header[4] != VERSION {
+        return Err(Error::InvalidIndex);
+    }
+    let buckets = u32::from_le_bytes(header[5..].try_into().expect("slice has 4 bytes"));
+
+    let mut found = None;
+    let mut raw = [0; ENTRY_SIZE as usize];
+    for _ in 0..buckets {
+        reader.read_exact(&mut raw).map_err(Error::ReadIndex)?;
+        let field = |range: std::ops::Range<usize>| {
+            let mut bytes = [0; 8];
+            bytes[..range.len()].copy_from_slice(&raw[range]);
+            u64::from_le_bytes(bytes)
+        };
+        if field(0..4) == length as u64 {
+            found = Some(Entry {
+                length: length as u32,
+                count: field(4..8) as u32,
+                offset: field(8..16),
+                size: field(16..24),
+            });
+        }
+    }
+    let entry = match found {
+        Some(entry) => entry,
+        None => return Ok(Vec::new()),
+    };
+    debug_assert_eq!(entry.length as usize, length);
+
+    let data_start = HEADER_SIZE + ENTRY_SIZE * buckets as u64;
+    reader
+        .seek(SeekFrom::Start(data_start + entry.offset))
+        .map_err(Error::ReadIndex)?;
+    let mut data = vec![0; entry.size as usize];
+    reader.read_exact(&mut data).map_err(Error::ReadIndex)?;
+    let data = String::from_utf8(data).map_err(|_| Error::InvalidIndex)?;
+
+    let words: Vec<String> = data.lines().map(ToOwned::to_owned).collect();
+    if words.len() != entry.count as usize {
+        return Err(Error::InvalidIndex);
+    }
+    Ok(words)
+}
+
+/// Load the words of a single length from a list.
+///
+/// This reads only the relevant bucket of the list's index, building the index first if it is
+/// missing or unreadable.
+pub fn load_length(list: List, length: usize) -> Result<Vec<String>, Error> {
+    if let Ok(words) = read(list, length) {
+        return Ok(words);
+    }
+    build(list)?;
+    read(list, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &[&str]) -> impl Iterator<Item = String> {
+        words
+            .iter()
+            .map(|&word| word.to_owned())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = tempfile::tempdir().expect("can create a temporary directory");
+        let path = dir.path().join("words.idx");
+        let buckets = write_path(
+            &path,
+            words(&["cigar", "ant", "café", "rebut", "bee", "abalone"]),
+        )
+        .expect("can write the index");
+        assert_eq!(buckets, 4);
+
+        let read = |length| read_path(&path, length).expect("can read the index");
+        assert_eq!(read(3), ["ant", "bee"]);
+        // lengths are in characters, not bytes
+        assert_eq!(read(4), ["café"]);
+        assert_eq!(read(5), ["cigar", "rebut"]);
+        assert_eq!(read(7), ["abalone"]);
+        assert!(read(6).is_empty());
+    }
+
+    #[test]
+    fn corrupt_index() {
+        let dir = tempfile::tempdir().expect("can create a temporary directory");
+        let path = dir.path().join("words.idx");
+        write_path(&path, words(&["cigar", "rebut", "ant"])).expect("can write the index");
+        let valid = std::fs::read(&path).expect("can read the index");
+
+        let corruptions: [fn(&mut Vec<u8>); 4] = [
+            // wrong magic bytes
+            |bytes| bytes[0] = b'X',
+            // unknown version
+            |bytes| bytes[4] = VERSION + 1,
+            // a newline missing, so the bucket has too few words
+            |bytes| *bytes.iter_mut().rev().nth(6).expect("index has data") = b'x',
+            // not UTF-8
+            |bytes| *bytes.last_mut().expect("index has data") = 0xff,
+        ];
+        for corrupt in corruptions {
+            let mut bytes = valid.clone();
+            corrupt(&mut bytes);
+            std::fs::write(&path, bytes).expect("can write the index");
+            assert!(
+                matches!(read_path(&path, 5), Err(Error::InvalidIndex)),
+                "{:?}",
+                read_path(&path, 5)
+            );
+        }
+    }
+}
@@ -0,0 +1,411 @@
+pub mod index;
+pub mod normalize;
+
+pub use index::load_length;
+pub use normalize::{Normalized, Normalizer, Report};
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+const WORDS_SOURCE: &str = "https://github.com/dwyl/english-words/raw/master/words_alpha.txt";
+const ANSWERS_SOURCE: &str = "https://github.com/first20hours/google-10000-english/raw/master/google-10000-english-no-swears.txt";
+const BUFFER_SIZE: usize = 4096;
+const COMPRESSION_LEVEL: i32 = 11; // Maximum, slowest, compression
+const WINDOW_SIZE: i32 = 24; // Most dense, most memory-intensive compression window
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const SPINNER_STYLE: spinners::Spinners = spinners::Spinners::Line;
+
+// It would be really great if we could `impl Drop` for SpinnerReader` to stop the spinner, but
+// the spinner implementaion doesn't permit that.
+struct SpinnerReader<R> {
+    inner: R,
+    read_so_far: usize,
+    total_expected: Option<usize>,
+    message: String,
+    spinner: spinners::Spinner,
+}
+
+impl<R> SpinnerReader<R> {
+    fn new(inner: R, message: String, total_expected: Option<usize>) -> Self {
+        let sr = SpinnerReader {
+            inner,
+            message,
+            read_so_far: 0,
+            total_expected,
+            spinner: spinners::Spinner::new(&SPINNER_STYLE, String::new()),
+        };
+        sr.update_message();
+        sr
+    }
+
+    fn update_message(&self) {
+        let message;
+        if let Some(total) = self.total_expected {
+            message = format!("{} ({}/{})", self.message, self.read_so_far, total);
+        } else {
+            message = format!("{} ({})", self.message, self.read_so_far);
+        }
+        self.spinner.message(message);
+    }
+}
+
+impl<R: Read> Read for SpinnerReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let result = self.inner.read(buf);
+        if let Ok(qty) = result {
+            self.read_so_far += qty;
+            self.update_message();
+        }
+        result
+    }
+}
+
+/// The word lists which can be loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum List {
+    /// Common words, suitable as secret targets.
+    Answers,
+    /// Every word which may be guessed, however obscure.
+    Allowed,
+}
+
+impl List {
+    /// Where this list is downloaded from.
+    fn source(self) -> &'static str {
+        match self {
+            List::Answers => ANSWERS_SOURCE,
+            List::Allowed => WORDS_SOURCE,
+        }
+    }
+
+    /// A human-readable name for this list.
+    pub fn name(self) -> &'static str {
+        match self {
+            List::Answers => "answer",
+            List::Allowed => "allowed guess",
+        }
+    }
+
+    /// The name of this list's file within the cache directory.
+    fn file_name(self) -> &'static str {
+        match self {
+            List::Answers => "answers.br",
+            List::Allowed => "words.br",
+        }
+    }
+}
+
+/// Where a word list comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// One of the standard lists, downloaded and cached as necessary.
+    Cached(List),
+    /// A local file; see [`load_path`].
+    Path(PathBuf),
+}
+
+impl Source {
+    /// Load the words from this source.
+    ///
+    /// Cached lists were normalized when they were cached, so are only trimmed; local files are
+    /// normalized according to [`Normalizer::default`].
+    pub fn load(&self) -> Result<Box<dyn Iterator<Item = String>>, Error> {
+        Ok(Box::new(self.load_with(&Normalizer::default())?))
+    }
+
+    /// Load the words from this source, normalizing a local file as specified.
+    ///
+    /// Cached lists were normalized when they were cached, so are only trimmed.
+    pub fn load_with(&self, normalizer: &Normalizer) -> Result<Normalized<Lines>, Error> {
+        Ok(match self {
+            Source::Cached(list) => Normalizer::trusted().apply(fetch_cache(*list)?),
+            Source::Path(path) => normalizer.apply(read_path(path)?),
+        })
+    }
+
+    /// Load only the words of this source which have the given number of characters.
+    ///
+    /// Cached lists are read through their index; see [`load_length`].
+    pub fn load_length(&self, length: usize) -> Result<Vec<String>, Error> {
+        self.load_length_with(length, &Normalizer::default())
+    }
+
+    /// As [`Source::load_length`], but normalize a local file as specified.
+    pub fn load_length_with(
+        &self,
+        length: usize,
+        normalizer: &Normalizer,
+    ) -> Result<Vec<String>, Error> {
+        match self {
+            Source::Cached(list) => load_length(*list, length),
+            Source::Path(_) => Ok(self
+                .load_with(normalizer)?
+                .filter(|word| word.chars().count() == length)
+                .collect()),
+        }
+    }
+}
+
+impl From<List> for Source {
+    fn from(list: List) -> Self {
+        Source::Cached(list)
+    }
+}
+
+/// The raw lines of a word list, before normalization.
+pub type Lines = Box<dyn Iterator<Item = String>>;
+
+/// Read a whole word list and split it into lines.
+///
+/// Reading everything up front means that a file which is not UTF-8, or which is truncated, is
+/// reported as an error rather than discovered part way through iteration.
+fn lines(mut reader: impl Read) -> std::io::Result<Lines> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let lines: Vec<String> = text.lines().map(ToOwned::to_owned).collect();
+    Ok(Box::new(lines.into_iter()))
+}
+
+/// Produce the cache path
+fn cache_path(list: List) -> Result<PathBuf, Error> {
+    let dir = dirs::cache_dir().ok_or(Error::NoCacheDir)?.join("wordler");
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).map_err(Error::CreateCacheDir)?;
+    }
+    Ok(dir.join(list.file_name()))
+}
+
+/// Read the cache from the canonical path without attempting to fetch it
+fn read_cache(list: List) -> Result<Lines, Error> {
+    let path = cache_path(list)?;
+    let reader = File::open(path).map_err(Error::OpenCache)?;
+    lines(brotli::Decompressor::new(reader, BUFFER_SIZE)).map_err(Error::ReadCache)
+}
+
+/// Read the cache, or download it fresh from the source and cache it.
+fn fetch_cache(list: List) -> Result<Lines, Error> {
+    let lines = read_cache(list);
+    if lines.is_ok() {
+        return lines;
+    }
+    create_cache(list, &Normalizer::default())?;
+    read_cache(list).map_err(|err| Error::InvalidCache(Box::new(err)))
+}
+
+/// Brotli-compress some words into the cache, one per line
+fn write_cache(list: List, words: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut text = String::new();
+    for word in words {
+        text.push_str(&word);
+        text.push('\n');
+    }
+
+    let path = cache_path(list)?;
+    let mut writer = File::create(path).map_err(Error::OpenCache)?;
+    let params = brotli::enc::BrotliEncoderParams {
+        mode: brotli::enc::backward_references::BrotliEncoderMode::BROTLI_MODE_TEXT,
+        quality: COMPRESSION_LEVEL,
+        lgwin: WINDOW_SIZE,
+        ..Default::default()
+    };
+    brotli::enc::BrotliCompress(&mut text.as_bytes(), &mut writer, &params)
+        .map_err(Error::Encode)?;
+    index::invalidate(list)
+}
+
+/// Download the cache from the source, clobbering any existing data
+fn create_cache(list: List, normalizer: &Normalizer) -> Result<Report, Error> {
+    let response = ureq::get(list.source())
+        .call()
+        .map_err(|err| Error::Download(Box::new(err)))?;
+    let length = response
+        .header("Content-Length")
+        .and_then(|l| l.parse().ok());
+    let mut reader = SpinnerReader::new(
+        response.into_reader(),
+        format!("downloading {} list", list.name()),
+        length,
+    );
+    let mut text = Vec::with_capacity(length.unwrap_or_default());
+    reader
+        .read_to_end(&mut text)
+        .map_err(|err| Error::Download(Box::new(err.into())))?;
+
+    // clean up the spinner
+    reader.spinner.stop();
+    println!();
+
+    let text =
+        lines(std::io::Cursor::new(text)).map_err(|err| Error::Download(Box::new(err.into())))?;
+    let mut words = normalizer.apply(text);
+    write_cache(list, words.by_ref())?;
+    Ok(words.report())
+}
+
+/// Load a word list from cache, or download it fresh from the source and cache it.
+///
+/// Words were normalized when they were cached, so are only trimmed.
+pub fn load(list: List) -> Result<impl Iterator<Item = String>, Error> {
+    Ok(Normalizer::trusted().apply(fetch_cache(list)?))
+}
+
+/// Download a word list fresh from the source and cache it, normalized as specified.
+pub fn download(list: List, normalizer: &Normalizer) -> Result<Report, Error> {
+    create_cache(list, normalizer)
+}
+
+/// Read the lines of a local file
+fn read_path(path: &Path) -> Result<Lines, Error> {
+    let open_err = |err| Error::OpenFile(path.to_owned(), err);
+    let read_err = |err| Error::ReadFile(path.to_owned(), err);
+
+    let mut reader = BufReader::new(File::open(path).map_err(open_err)?);
+    let is_gzip = reader
+        .fill_buf()
+        .map_err(open_err)?
+        .starts_with(&GZIP_MAGIC);
+    if is_gzip {
+        lines(flate2::bufread::MultiGzDecoder::new(reader))
+    } else if path.extension().map(|ext| ext == "br").unwrap_or_default() {
+        lines(brotli::Decompressor::new(reader, BUFFER_SIZE))
+    } else {
+        lines(reader)
+    }
+    .map_err(read_err)
+}
+
+/// Load a word list from a local file of one word per line.
+///
+/// The file may be plain text, gzip-compressed, or brotli-compressed; brotli files are recognized
+/// by a `.br` extension. Words are normalized according to [`Normalizer::default`].
+pub fn load_path(path: impl AsRef<Path>) -> Result<impl Iterator<Item = String>, Error> {
+    Ok(Normalizer::default().apply(read_path(path.as_ref())?))
+}
+
+/// Load the words of the given length from `answers` which also appear in `allowed`.
+///
+/// These are the words suitable as targets: a solver drawing its guesses from `allowed` can always
+/// find them.
+pub fn answers(answers: &Source, allowed: &Source, length: usize) -> Result<Vec<String>, Error> {
+    answers_with(answers, allowed, length, &Normalizer::default())
+}
+
+/// As [`answers`], but normalize local files as specified.
+pub fn answers_with(
+    answers: &Source,
+    allowed: &Source,
+    length: usize,
+    normalizer: &Normalizer,
+) -> Result<Vec<String>, Error> {
+    let allowed: HashSet<String> = allowed
+        .load_length_with(length, normalizer)?
+        .into_iter()
+        .collect();
+    let mut answers = answers.load_length_with(length, normalizer)?;
+    answers.retain(|word| allowed.contains(word));
+    Ok(answers)
+}
+
+/// Replace a cached word list with the contents of a local file, without downloading anything.
+///
+/// The file is read as by [`load_path`], but normalized as specified.
+pub fn import(
+    list: List,
+    path: impl AsRef<Path>,
+    normalizer: &Normalizer,
+) -> Result<Report, Error> {
+    let mut words = normalizer.apply(read_path(path.as_ref())?);
+    write_cache(list, words.by_ref())?;
+    Ok(words.report())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no cache directory could be constructed")]
+    NoCacheDir,
+    #[error("could not create the cache directory")]
+    CreateCacheDir(#[source] std::io::Error),
+    #[error("could not open the cache file")]
+    OpenCache(#[source] std::io::Error),
+    #[error("could not read the cache file")]
+    ReadCache(#[source] std::io::Error),
+    #[error("could not download the word list")]
+    Download(#[source] Box<ureq::Error>),
+    #[error("could not brotli-compress the cache file")]
+    Encode(#[source] std::io::Error),
+    #[error("could not open word list {}", .0.display())]
+    OpenFile(PathBuf, #[source] std::io::Error),
+    #[error("could not read word list {}", .0.display())]
+    ReadFile(PathBuf, #[source] std::io::Error),
+    #[error("could not write the word list index")]
+    WriteIndex(#[source] std::io::Error),
+    #[error("could not read the word list index")]
+    ReadIndex(#[source] std::io::Error),
+    #[error("the word list index is corrupt")]
+    InvalidIndex,
+    #[error("cache not valid after download")]
+    InvalidCache(#[source] Box<Error>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const TEXT: &str = "Cigar\r\nrebut\n\ncigar\nsissy\n";
+
+    fn gzip(text: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(text.as_bytes()).expect("can compress");
+        encoder.finish().expect("can compress")
+    }
+
+    fn brotli(text: &str) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut compressed, BUFFER_SIZE, 5, 22);
+            writer.write_all(text.as_bytes()).expect("can compress");
+        }
+        compressed
+    }
+
+    fn load_file(name: &str, contents: &[u8]) -> Result<Vec<String>, Error> {
+        let dir = tempfile::tempdir().expect("can create a temporary directory");
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).expect("can write the word list");
+        let words = load_path(&path)?.collect();
+        Ok(words)
+    }
+
+    #[test]
+    fn loads_local_files() {
+        let expected = ["cigar", "rebut", "sissy"];
+        assert_eq!(load_file("words.txt", TEXT.as_bytes()).unwrap(), expected);
+        assert_eq!(load_file("words.txt.gz", &gzip(TEXT)).unwrap(), expected);
+        // gzip is recognized by its contents, whatever the name
+        assert_eq!(load_file("words.txt", &gzip(TEXT)).unwrap(), expected);
+        assert_eq!(load_file("words.br", &brotli(TEXT)).unwrap(), expected);
+    }
+
+    #[test]
+    fn reports_unreadable_files() {
+        let mut truncated = gzip(TEXT);
+        truncated.truncate(truncated.len() / 2);
+        assert!(matches!(
+            load_file("words.gz", &truncated),
+            Err(Error::ReadFile(..))
+        ));
+        assert!(matches!(
+            load_file("words.txt", b"caf\xe9\n"),
+            Err(Error::ReadFile(..))
+        ));
+        assert!(matches!(
+            load_path("/nonexistent/words.txt").map(|_| ()),
+            Err(Error::OpenFile(..))
+        ));
+    }
+}
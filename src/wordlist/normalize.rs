@@ -0,0 +1,283 @@
+//! Clean up raw word lists so that every word is comparable, character for character.
+
+use std::collections::HashSet;
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// The alphabet of the standard word lists.
+pub const ENGLISH_ALPHABET: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// Settings for cleaning up a raw word list.
+///
+/// Surrounding whitespace, including the `\r` of CRLF line endings, is always trimmed. The other
+/// rules are applied in the order of the fields below.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Normalizer {
+    /// Compose each word into Unicode normalization form C.
+    pub nfc: bool,
+    /// Fold each word to lower case.
+    pub lowercase: bool,
+    /// Remove combining diacritical marks, so that "café" becomes "cafe".
+    pub strip_diacritics: bool,
+    /// When set, drop words containing any character outside this alphabet.
+    pub alphabet: Option<String>,
+    /// Drop repeated words, keeping only the first of each.
+    pub dedup: bool,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Normalizer {
+            nfc: true,
+            lowercase: true,
+            strip_diacritics: false,
+            alphabet: Some(ENGLISH_ALPHABET.into()),
+            dedup: true,
+        }
+    }
+}
+
+impl Normalizer {
+    /// Settings which only trim words and drop blank lines.
+    ///
+    /// This suits lists which were normalized when they were written, such as the caches.
+    pub fn trusted() -> Self {
+        Normalizer {
+            nfc: false,
+            lowercase: false,
+            strip_diacritics: false,
+            alphabet: None,
+            dedup: false,
+        }
+    }
+
+    /// Normalize a single word, or `None` if it is blank or falls outside the alphabet.
+    ///
+    /// Deduplication requires a whole list; use [`Normalizer::apply`] for that.
+    pub fn normalize(&self, word: &str) -> Option<String> {
+        self.normalize_inner(word).ok()
+    }
+
+    fn normalize_inner(&self, word: &str) -> Result<String, Rule> {
+        let mut word = word.trim().to_owned();
+        if self.nfc {
+            word = word.nfc().collect();
+        }
+        if self.lowercase {
+            word = word.to_lowercase();
+        }
+        if self.strip_diacritics {
+            word = word
+                .nfd()
+                .filter(|&ch| !is_combining_mark(ch))
+                .nfc()
+                .collect();
+        }
+        if word.is_empty() {
+            return Err(Rule::Empty);
+        }
+        if let Some(alphabet) = &self.alphabet {
+            if word.chars().any(|ch| !alphabet.contains(ch)) {
+                return Err(Rule::Alphabet);
+            }
+        }
+        Ok(word)
+    }
+
+    /// Apply a command-line flag which changes these settings, taking its value from `args`.
+    ///
+    /// Returns whether `flag` was one of `--strip-diacritics`, `--alphabet LETTERS`, or
+    /// `--keep-duplicates`.
+    pub fn parse_flag(
+        &mut self,
+        flag: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, String> {
+        match flag {
+            "--strip-diacritics" => self.strip_diacritics = true,
+            "--alphabet" => self.alphabet = Some(args.next().ok_or("--alphabet requires letters")?),
+            "--keep-duplicates" => self.dedup = false,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Normalize a list of words.
+    ///
+    /// The returned iterator keeps a [`Report`] of the words it has dropped so far.
+    pub fn apply<I>(&self, words: I) -> Normalized<I::IntoIter>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Normalized {
+            inner: words.into_iter(),
+            normalizer: self.clone(),
+            seen: HashSet::new(),
+            report: Report::default(),
+        }
+    }
+}
+
+/// The rule which dropped a word.
+enum Rule {
+    Empty,
+    Alphabet,
+}
+
+/// How many words each normalization rule dropped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// words which survived normalization
+    pub kept: usize,
+    /// lines which were blank
+    pub empty: usize,
+    /// words with characters outside the alphabet
+    pub alphabet: usize,
+    /// words which repeated an earlier word
+    pub duplicate: usize,
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "kept {} words; dropped {} blank, {} outside the alphabet, {} duplicate",
+            self.kept, self.empty, self.alphabet, self.duplicate
+        )
+    }
+}
+
+/// An iterator of normalized words; see [`Normalizer::apply`].
+pub struct Normalized<I> {
+    inner: I,
+    normalizer: Normalizer,
+    seen: HashSet<String>,
+    report: Report,
+}
+
+impl<I> Normalized<I> {
+    /// How many words have been kept and dropped so far.
+    pub fn report(&self) -> Report {
+        self.report
+    }
+}
+
+impl<I> Iterator for Normalized<I>
+where
+    I: Iterator<Item = String>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for raw in self.inner.by_ref() {
+            match self.normalizer.normalize_inner(&raw) {
+                Err(Rule::Empty) => self.report.empty += 1,
+                Err(Rule::Alphabet) => self.report.alphabet += 1,
+                Ok(word) => {
+                    if self.normalizer.dedup && !self.seen.insert(word.clone()) {
+                        self.report.duplicate += 1;
+                    } else {
+                        self.report.kept += 1;
+                        return Some(word);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Normalize `words`, returning the kept words and the report.
+    fn run(normalizer: &Normalizer, words: &[&str]) -> (Vec<String>, Report) {
+        let mut normalized = normalizer.apply(words.iter().map(|&word| word.to_owned()));
+        let kept = normalized.by_ref().collect();
+        (kept, normalized.report())
+    }
+
+    #[test]
+    fn trims_crlf_and_drops_blank_lines() {
+        let (kept, report) = run(
+            &Normalizer::default(),
+            &["cigar\r", " Rebut\r", "\r", "", "SISSY", "cigar"],
+        );
+        assert_eq!(kept, ["cigar", "rebut", "sissy"]);
+        assert_eq!(
+            report,
+            Report {
+                kept: 3,
+                empty: 2,
+                alphabet: 0,
+                duplicate: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn composes_before_checking_the_alphabet() {
+        // "café" precomposed, then with a combining acute accent
+        let words = ["caf\u{e9}", "cafe\u{301}", "cafe"];
+        let accented = Normalizer {
+            alphabet: Some(format!("{}\u{e9}", ENGLISH_ALPHABET)),
+            ..Normalizer::default()
+        };
+        let (kept, report) = run(&accented, &words);
+        assert_eq!(kept, ["caf\u{e9}", "cafe"]);
+        assert_eq!((report.kept, report.duplicate), (2, 1));
+
+        // without composition, the combining mark is outside the alphabet
+        let (kept, report) = run(
+            &Normalizer {
+                nfc: false,
+                ..accented
+            },
+            &words,
+        );
+        assert_eq!(kept, ["caf\u{e9}", "cafe"]);
+        assert_eq!((report.kept, report.alphabet), (2, 1));
+    }
+
+    #[test]
+    fn strips_diacritics() {
+        let words = ["caf\u{e9}", "cafe\u{301}", "na\u{ef}ve", "cafe"];
+        let (kept, report) = run(&Normalizer::default(), &words);
+        assert_eq!(kept, ["cafe"]);
+        assert_eq!((report.kept, report.alphabet), (1, 3));
+
+        let stripping = Normalizer {
+            strip_diacritics: true,
+            ..Normalizer::default()
+        };
+        let (kept, report) = run(&stripping, &words);
+        assert_eq!(kept, ["cafe", "naive"]);
+        assert_eq!(
+            report,
+            Report {
+                kept: 2,
+                empty: 0,
+                alphabet: 0,
+                duplicate: 2,
+            }
+        );
+
+        let (kept, report) = run(
+            &Normalizer {
+                dedup: false,
+                ..stripping
+            },
+            &words,
+        );
+        assert_eq!(kept, ["cafe", "cafe", "naive", "cafe"]);
+        assert_eq!(report.duplicate, 0);
+    }
+
+    #[test]
+    fn trusted_only_trims() {
+        let (kept, report) = run(&Normalizer::trusted(), &["Caf\u{e9}\r", "  ", "Caf\u{e9}"]);
+        assert_eq!(kept, ["Caf\u{e9}", "Caf\u{e9}"]);
+        assert_eq!((report.kept, report.empty), (2, 1));
+    }
+}